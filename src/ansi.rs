@@ -0,0 +1,288 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! A small ANSI terminal emulator for replaying recorded output.
+//!
+//! It understands what terminals are usually sent by full-screen programs
+//! like cosmostrix itself (cursor movement, erase and SGR colors) and ignores
+//! the rest. Lines do not wrap: text past the right edge is dropped.
+
+use crossterm::style::Color;
+
+use crate::cell::Cell;
+use crate::frame::Frame;
+
+/// Screen contents produced by feeding it terminal output.
+pub struct VirtualScreen {
+    frame: Frame,
+    x: u16,
+    y: u16,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    bold: bool,
+    /// An escape sequence split across two [`VirtualScreen::feed`] calls.
+    pending: String,
+}
+
+impl VirtualScreen {
+    /// A blank `width` x `height` screen with the cursor at the top left.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            frame: Frame::new(width, height, None),
+            x: 0,
+            y: 0,
+            fg: None,
+            bg: None,
+            bold: false,
+            pending: String::new(),
+        }
+    }
+
+    /// Current contents.
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Changes the size, keeping the top-left part of the contents.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        let mut frame = Frame::new(width, height, None);
+        for y in 0..height.min(self.frame.height) {
+            for x in 0..width.min(self.frame.width) {
+                if let Some(c) = self.frame.get(x, y) {
+                    frame.set(x, y, *c);
+                }
+            }
+        }
+        self.frame = frame;
+        self.x = self.x.min(width.saturating_sub(1));
+        self.y = self.y.min(height.saturating_sub(1));
+    }
+
+    /// Interprets `text` as terminal output.
+    pub fn feed(&mut self, text: &str) {
+        let mut buf = std::mem::take(&mut self.pending);
+        buf.push_str(text);
+        let mut chars = buf.char_indices().peekable();
+
+        while let Some((start, ch)) = chars.next() {
+            match ch {
+                '\x1b' => match chars.peek().map(|&(_, c)| c) {
+                    Some('[') => {
+                        chars.next();
+                        let mut params = String::new();
+                        let mut fin = None;
+                        for (_, c) in chars.by_ref() {
+                            if ('\x40'..='\x7e').contains(&c) {
+                                fin = Some(c);
+                                break;
+                            }
+                            params.push(c);
+                        }
+                        match fin {
+                            Some(f) => self.csi(&params, f),
+                            None => {
+                                self.pending = buf[start..].to_string();
+                                return;
+                            }
+                        }
+                    }
+                    Some(_) => {
+                        chars.next();
+                    }
+                    None => {
+                        self.pending = buf[start..].to_string();
+                        return;
+                    }
+                },
+                '\r' => self.x = 0,
+                '\n' => self.y = (self.y + 1).min(self.frame.height.saturating_sub(1)),
+                '\x08' => self.x = self.x.saturating_sub(1),
+                c if c.is_control() => {}
+                c => {
+                    let cell = Cell {
+                        ch: c,
+                        fg: self.fg,
+                        bg: self.bg,
+                        bold: self.bold,
+                    };
+                    self.frame.set(self.x, self.y, cell);
+                    self.x = self.x.saturating_add(1);
+                }
+            }
+        }
+    }
+
+    fn clear_range(&mut self, from: usize, to: usize) {
+        let w = self.frame.width as usize;
+        if w == 0 {
+            return;
+        }
+        let blank = Cell::blank_with_bg(self.bg);
+        for i in from..to {
+            self.frame.set((i % w) as u16, (i / w) as u16, blank);
+        }
+    }
+
+    fn csi(&mut self, params: &str, fin: char) {
+        if params.starts_with('?') {
+            return;
+        }
+        let nums: Vec<u16> = params
+            .split([';', ':'])
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let arg =
+            |i: usize, default: u16| nums.get(i).copied().filter(|&n| n != 0).unwrap_or(default);
+        let max_x = self.frame.width.saturating_sub(1);
+        let max_y = self.frame.height.saturating_sub(1);
+        let w = self.frame.width as usize;
+        let total = w * self.frame.height as usize;
+        let cursor = self.y as usize * w + self.x.min(max_x) as usize;
+
+        match fin {
+            'H' | 'f' => {
+                self.y = (arg(0, 1) - 1).min(max_y);
+                self.x = (arg(1, 1) - 1).min(max_x);
+            }
+            'A' => self.y = self.y.saturating_sub(arg(0, 1)),
+            'B' => self.y = self.y.saturating_add(arg(0, 1)).min(max_y),
+            'C' => self.x = self.x.saturating_add(arg(0, 1)).min(max_x),
+            'D' => self.x = self.x.saturating_sub(arg(0, 1)),
+            'G' => self.x = (arg(0, 1) - 1).min(max_x),
+            'd' => self.y = (arg(0, 1) - 1).min(max_y),
+            'J' => match nums.first().copied().unwrap_or(0) {
+                0 => self.clear_range(cursor, total),
+                1 => self.clear_range(0, cursor + 1),
+                _ => self.clear_range(0, total),
+            },
+            'K' => {
+                let line = self.y as usize * w;
+                match nums.first().copied().unwrap_or(0) {
+                    0 => self.clear_range(cursor, line + w),
+                    1 => self.clear_range(line, cursor + 1),
+                    _ => self.clear_range(line, line + w),
+                }
+            }
+            'm' => self.sgr(&nums),
+            _ => {}
+        }
+    }
+
+    fn sgr(&mut self, nums: &[u16]) {
+        let mut i = 0;
+        while i < nums.len() {
+            match nums[i] {
+                0 => {
+                    self.fg = None;
+                    self.bg = None;
+                    self.bold = false;
+                }
+                1 => self.bold = true,
+                22 => self.bold = false,
+                n @ 30..=37 => self.fg = Some(ansi_color(n - 30)),
+                n @ 90..=97 => self.fg = Some(ansi_color(n - 90 + 8)),
+                39 => self.fg = None,
+                n @ 40..=47 => self.bg = Some(ansi_color(n - 40)),
+                n @ 100..=107 => self.bg = Some(ansi_color(n - 100 + 8)),
+                49 => self.bg = None,
+                n @ (38 | 48) => {
+                    let (color, used) = match nums.get(i + 1) {
+                        Some(5) => (nums.get(i + 2).map(|&v| indexed_color(v)), 2),
+                        Some(2) => match nums.get(i + 2..i + 5) {
+                            Some(&[r, g, b]) => (
+                                Some(Color::Rgb {
+                                    r: r as u8,
+                                    g: g as u8,
+                                    b: b as u8,
+                                }),
+                                4,
+                            ),
+                            _ => (None, 4),
+                        },
+                        _ => (None, 0),
+                    };
+                    if let Some(c) = color {
+                        if n == 38 {
+                            self.fg = Some(c);
+                        } else {
+                            self.bg = Some(c);
+                        }
+                    }
+                    i += used;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// `38;5;N` colors; crossterm sends its named colors this way too, so the
+/// first 16 map back to them.
+fn indexed_color(n: u16) -> Color {
+    if n < 16 {
+        ansi_color(n)
+    } else {
+        Color::AnsiValue(n.min(255) as u8)
+    }
+}
+
+fn ansi_color(n: u16) -> Color {
+    match n {
+        0 => Color::Black,
+        1 => Color::DarkRed,
+        2 => Color::DarkGreen,
+        3 => Color::DarkYellow,
+        4 => Color::DarkBlue,
+        5 => Color::DarkMagenta,
+        6 => Color::DarkCyan,
+        7 => Color::Grey,
+        8 => Color::DarkGrey,
+        9 => Color::Red,
+        10 => Color::Green,
+        11 => Color::Yellow,
+        12 => Color::Blue,
+        13 => Color::Magenta,
+        14 => Color::Cyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::terminal::{Backend, MemoryBackend};
+
+    #[test]
+    fn replays_renderer_output() {
+        let mut term = MemoryBackend::new(6, 3);
+        let mut frame = Frame::new(6, 3, Some(Color::Black));
+        let green = Cell {
+            ch: 'x',
+            fg: Some(Color::Rgb { r: 0, g: 200, b: 0 }),
+            bg: Some(Color::Black),
+            bold: true,
+        };
+        frame.set(2, 1, green);
+        frame.set(
+            5,
+            2,
+            Cell {
+                ch: 'ア',
+                fg: Some(Color::AnsiValue(40)),
+                ..green
+            },
+        );
+        term.draw(&mut frame).unwrap();
+        frame.set(3, 1, Cell { ch: 'y', ..green });
+        term.draw(&mut frame).unwrap();
+
+        let mut screen = VirtualScreen::new(6, 3);
+        let bytes = String::from_utf8(term.take_bytes()).unwrap();
+        // Split mid-sequence to check that partial escapes are carried over.
+        let (a, b) = bytes.split_at(bytes.find('\x1b').unwrap() + 2);
+        screen.feed(a);
+        screen.feed(b);
+        assert_eq!(screen.frame().cells, frame.cells);
+    }
+}
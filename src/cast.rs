@@ -0,0 +1,216 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! asciicast v2 recording (the asciinema file format).
+//!
+//! A recording is a JSON header line followed by one JSON array per event:
+//! `[seconds, "o", "bytes"]` for output and `[seconds, "r", "COLSxLINES"]` for
+//! resizes. See <https://docs.asciinema.org/manual/asciicast/v2/>.
+//!
+//! [`CastWriter`] writes recordings and [`parse_cast`] reads them back.
+
+use std::io::{Result, Write};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+/// Writes asciicast v2 events to `W`, timestamped from creation.
+pub struct CastWriter<W: Write> {
+    out: W,
+    start: Instant,
+}
+
+impl<W: Write> CastWriter<W> {
+    /// Writes the header for a `width` x `height` terminal.
+    pub fn new(mut out: W, width: u16, height: u16) -> Result<Self> {
+        let mut header = format!(
+            "{{\"version\": 2, \"width\": {}, \"height\": {}",
+            width, height
+        );
+        if let Ok(d) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            header.push_str(&format!(", \"timestamp\": {}", d.as_secs()));
+        }
+        let term = std::env::var("TERM").unwrap_or_default();
+        if !term.is_empty() {
+            header.push_str(&format!(", \"env\": {{\"TERM\": {}}}", json_string(&term)));
+        }
+        header.push_str("}\n");
+        out.write_all(header.as_bytes())?;
+        Ok(Self {
+            out,
+            start: Instant::now(),
+        })
+    }
+
+    /// Records bytes written to the terminal.
+    pub fn output(&mut self, bytes: &[u8]) -> Result<()> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        self.event("o", &String::from_utf8_lossy(bytes))
+    }
+
+    /// Records a terminal resize.
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
+        self.event("r", &format!("{}x{}", width, height))
+    }
+
+    /// Flushes buffered events.
+    pub fn flush(&mut self) -> Result<()> {
+        self.out.flush()
+    }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+
+    fn event(&mut self, code: &str, data: &str) -> Result<()> {
+        let t = self.start.elapsed().as_secs_f64();
+        let line = format!("[{:.6}, \"{}\", {}]\n", t, code, json_string(data));
+        self.out.write_all(line.as_bytes())
+    }
+}
+
+/// `s` as a quoted, escaped JSON string.
+fn json_string(s: &str) -> String {
+    Value::from(s).to_string()
+}
+
+/// One recorded event.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CastEvent {
+    /// Bytes written to the terminal.
+    Output(String),
+    /// The terminal was resized to `(cols, lines)`.
+    Resize(u16, u16),
+}
+
+/// A parsed recording: initial size and timestamped events, in file order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cast {
+    pub width: u16,
+    pub height: u16,
+    /// `(seconds since start, event)`; input and marker events are dropped.
+    pub events: Vec<(f64, CastEvent)>,
+}
+
+impl Cast {
+    /// Length of the recording in seconds.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map(|(t, _)| *t).unwrap_or(0.0)
+    }
+}
+
+/// Parses an asciicast v2 file. Errors name the offending line.
+pub fn parse_cast(text: &str) -> std::result::Result<Cast, String> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().ok_or("empty recording")?;
+    let header = match serde_json::from_str(header) {
+        Ok(Value::Object(fields)) => fields,
+        Ok(_) => return Err("line 1: header is not a JSON object".to_string()),
+        Err(e) => return Err(format!("line 1: invalid JSON: {}", e)),
+    };
+    let field = |name: &str| header.get(name).and_then(Value::as_f64);
+    match field("version") {
+        Some(v) if v == 2.0 => {}
+        _ => return Err("line 1: not an asciicast v2 recording".to_string()),
+    }
+    let dim = |name: &str| match field(name) {
+        Some(v) if (1.0..=u16::MAX as f64).contains(&v) => Ok(v as u16),
+        _ => Err(format!("line 1: missing or invalid \"{}\"", name)),
+    };
+    let mut cast = Cast {
+        width: dim("width")?,
+        height: dim("height")?,
+        events: Vec::new(),
+    };
+
+    for (i, line) in lines {
+        let err = |msg: &str| format!("line {}: {}", i + 1, msg);
+        let items = match serde_json::from_str(line) {
+            Ok(Value::Array(items)) => items,
+            Ok(_) => return Err(err("event is not a JSON array")),
+            Err(e) => return Err(err(&format!("invalid JSON: {}", e))),
+        };
+        let (t, code, data) = match items.as_slice() {
+            [t, Value::String(code), Value::String(data)] => match t.as_f64() {
+                Some(t) => (t, code, data),
+                None => return Err(err("expected [time, code, data]")),
+            },
+            _ => return Err(err("expected [time, code, data]")),
+        };
+        if !t.is_finite() || t < 0.0 {
+            return Err(err("invalid event time"));
+        }
+        let ev = match code.as_str() {
+            "o" => CastEvent::Output(data.clone()),
+            "r" => {
+                let (w, h) = data
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.parse().ok()?, h.parse().ok()?)))
+                    .ok_or_else(|| err("invalid resize, expected COLSxLINES"))?;
+                CastEvent::Resize(w, h)
+            }
+            _ => continue,
+        };
+        cast.events.push((t, ev));
+    }
+    Ok(cast)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_header_and_escaped_events() {
+        let mut cast = CastWriter::new(Vec::new(), 80, 24).unwrap();
+        cast.output(b"\x1b[1;1H\"a\\b\"").unwrap();
+        cast.resize(100, 30).unwrap();
+        let text = String::from_utf8(cast.into_inner()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 80, \"height\": 24"));
+        assert!(lines[1].ends_with(", \"o\", \"\\u001b[1;1H\\\"a\\\\b\\\"\"]"));
+        assert!(lines[2].ends_with(", \"r\", \"100x30\"]"));
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn parses_what_it_writes() {
+        let mut cast = CastWriter::new(Vec::new(), 20, 5).unwrap();
+        cast.output("\x1b[2J\"ア\\\u{1}".as_bytes()).unwrap();
+        cast.resize(30, 8).unwrap();
+        let text = String::from_utf8(cast.into_inner()).unwrap();
+        let parsed = parse_cast(&text).unwrap();
+
+        assert_eq!((parsed.width, parsed.height), (20, 5));
+        assert_eq!(
+            parsed
+                .events
+                .iter()
+                .map(|(_, e)| e.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                CastEvent::Output("\x1b[2J\"ア\\\u{1}".to_string()),
+                CastEvent::Resize(30, 8),
+            ]
+        );
+        assert!(parse_cast("{\"version\": 1}").is_err());
+        assert!(
+            parse_cast("{\"version\": 2, \"width\": 2, \"height\": 2}\n[0.5, \"o\"]")
+                .unwrap_err()
+                .starts_with("line 2:")
+        );
+
+        let deep = format!(
+            "{{\"version\": 2, \"width\": 2, \"height\": 2}}\n{}",
+            "[".repeat(100_000)
+        );
+        let err = parse_cast(&deep).unwrap_err();
+        assert!(err.starts_with("line 2: invalid JSON:"), "{}", err);
+    }
+}
@@ -1,7 +1,11 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! A single terminal cell.
+
 use crossterm::style::Color;
 
+/// One character position on screen: glyph, colors and weight.
+/// `None` colors mean the terminal default.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Cell {
     pub ch: char,
@@ -11,6 +15,7 @@ pub struct Cell {
 }
 
 impl Cell {
+    /// A space with no foreground, on `bg`.
     pub fn blank_with_bg(bg: Option<Color>) -> Self {
         Self {
             ch: ' ',
@@ -1,7 +1,13 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! Character sets the rain is drawn from.
+
 use std::char;
+use std::collections::{HashMap, HashSet};
+
+use crate::script::find_script;
 
+/// Bit set of glyph groups; combine presets with [`charset_from_str`].
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Charset(u32);
 
@@ -30,11 +36,13 @@ impl Charset {
     pub const ASCII_SAFE: Charset = Charset(0x3);
     pub const MATRIX: Charset = Charset(0xB);
 
+    /// True if any group in `other` is also in `self`.
     pub fn contains(self, other: Charset) -> bool {
         (self.0 & other.0) != 0
     }
 }
 
+/// Parses `--chars` input: comma-separated hex code points.
 pub fn parse_user_hex_chars(s: &str) -> Result<Vec<char>, String> {
     let mut out = Vec::new();
     for (i, part) in s.split(',').enumerate() {
@@ -51,6 +59,85 @@ pub fn parse_user_hex_chars(s: &str) -> Result<Vec<char>, String> {
     Ok(out)
 }
 
+/// A code point written as `U+30A0` or plain hex `30A0`.
+fn parse_code_point(s: &str) -> Result<char, String> {
+    let hex = s
+        .strip_prefix("U+")
+        .or_else(|| s.strip_prefix("u+"))
+        .unwrap_or(s);
+    let v = u32::from_str_radix(hex, 16).map_err(|_| format!("invalid code point: {}", s))?;
+    char::from_u32(v).ok_or_else(|| format!("invalid unicode scalar: {}", s))
+}
+
+/// Parses `--chars` into inclusive ranges. Items are ranges such as
+/// `U+30A0..U+30FF` or single code points such as `U+2588`; a list of plain
+/// hex code points without `U+` or `..` is read as pairs (`30,39,41,5A`).
+pub fn parse_char_ranges(s: &str) -> Result<Vec<(char, char)>, String> {
+    let items: Vec<&str> = s
+        .split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .collect();
+    let ranges = if items
+        .iter()
+        .any(|p| p.contains("..") || p.to_ascii_uppercase().starts_with("U+"))
+    {
+        items
+            .iter()
+            .map(|item| match item.split_once("..") {
+                Some((a, b)) => {
+                    let (a, b) = (parse_code_point(a.trim())?, parse_code_point(b.trim())?);
+                    if a > b {
+                        return Err(format!("range {} is backwards", item));
+                    }
+                    Ok((a, b))
+                }
+                None => parse_code_point(item).map(|c| (c, c)),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        let list = parse_user_hex_chars(s)?;
+        if list.len() % 2 != 0 {
+            return Err("odd number of unicode chars given (must be even)".to_string());
+        }
+        list.chunks(2).map(|p| (p[0], p[1])).collect()
+    };
+    for &(a, b) in &ranges {
+        if let Some(c) = (a..=b).find(|c| c.is_control()) {
+            return Err(format!(
+                "U+{:04X}..U+{:04X} includes control character U+{:04X}",
+                a as u32, b as u32, c as u32
+            ));
+        }
+    }
+    Ok(ranges)
+}
+
+/// The glyphs of `--chars-literal` or a `--chars-file`, in order. Line
+/// breaks are skipped; any other control character is an error.
+pub fn parse_literal_chars(text: &str) -> Result<Vec<char>, String> {
+    let mut out = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            if c.is_control() {
+                return Err(format!(
+                    "control character U+{:04X} at line {}, column {}",
+                    c as u32,
+                    i + 1,
+                    j + 1
+                ));
+            }
+            out.push(c);
+        }
+    }
+    if out.is_empty() {
+        return Err("no characters given".to_string());
+    }
+    Ok(out)
+}
+
+/// Resolves a preset name (see `--list-charsets`). `auto` picks an ASCII-safe
+/// set when `default_to_ascii` is true.
 pub fn charset_from_str(spec: &str, default_to_ascii: bool) -> Result<Charset, String> {
     let spec = spec.trim().to_ascii_lowercase();
     match spec.as_str() {
@@ -101,6 +188,191 @@ pub fn charset_from_str(spec: &str, default_to_ascii: bool) -> Result<Charset, S
     }
 }
 
+/// Splits the `:WEIGHT` suffix off a charset expression term; the colon of
+/// `script:NAME` is not one.
+fn split_weight(term: &str) -> (&str, Option<&str>) {
+    match term.rsplit_once(':') {
+        Some((expr, w)) if !expr.trim_end().to_ascii_lowercase().ends_with("script") => {
+            (expr, Some(w))
+        }
+        _ => (term, None),
+    }
+}
+
+/// Glyphs of a preset name or of `script:NAME` (see [`crate::script`]).
+fn named_chars(name: &str, default_to_ascii: bool) -> Result<Vec<char>, String> {
+    match name.split_once(':') {
+        Some((prefix, script)) if prefix.trim().eq_ignore_ascii_case("script") => {
+            find_script(script).map(|s| s.chars()).ok_or_else(|| {
+                format!("unsupported script: {} (see --list-scripts)", script.trim())
+            })
+        }
+        _ => Ok(build_chars(
+            charset_from_str(name, default_to_ascii)?,
+            &[],
+            default_to_ascii,
+        )),
+    }
+}
+
+/// Glyphs of one term of a charset expression: presets joined with `+`
+/// (union) and `-` (difference), left to right, e.g. `matrix+greek-punc`.
+fn charset_term(expr: &str, default_to_ascii: bool) -> Result<Vec<char>, String> {
+    let mut out: Vec<char> = Vec::new();
+    let mut rest = expr.trim();
+    let mut remove = false;
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let name = rest[..end].trim();
+        if name.is_empty() {
+            return Err(format!("missing charset name in `{}`", expr.trim()));
+        }
+        let glyphs = named_chars(name, default_to_ascii)?;
+        if remove {
+            out.retain(|c| !glyphs.contains(c));
+        } else {
+            out.extend(glyphs);
+        }
+        if end == rest.len() {
+            break;
+        }
+        remove = rest.as_bytes()[end] == b'-';
+        rest = &rest[end + 1..];
+    }
+    let mut seen = HashSet::new();
+    out.retain(|&c| seen.insert(c));
+    if out.is_empty() {
+        return Err(format!("`{}` leaves no characters", expr.trim()));
+    }
+    Ok(out)
+}
+
+/// Every preset a charset expression names, ignoring weights and whether it
+/// is added or removed. Scripts are not presets and add nothing.
+pub fn charset_expr_groups(spec: &str, default_to_ascii: bool) -> Result<Charset, String> {
+    let mut groups = Charset::NONE;
+    for term in spec.split(',') {
+        for name in split_weight(term).0.split(['+', '-']) {
+            if name.trim().to_ascii_lowercase().starts_with("script:") {
+                named_chars(name, default_to_ascii)?;
+                continue;
+            }
+            groups.0 |= charset_from_str(name, default_to_ascii)?.0;
+        }
+    }
+    Ok(groups)
+}
+
+/// Glyphs of a `--charset` value with their sampling weights.
+///
+/// Besides a preset name the value can be an expression: `+` and `-`
+/// combine presets as sets (`matrix+greek-punc`), and comma-separated terms
+/// with a `:WEIGHT` suffix get that share of the rain (`katakana:3,digits:1`
+/// is three quarters katakana). Without weights every glyph is equally
+/// likely. `script:NAME` can stand in for any preset. `user_ranges` are one
+/// more term, of weight 1.
+pub fn weighted_chars_from_str(
+    spec: &str,
+    user_ranges: &[(char, char)],
+    default_to_ascii: bool,
+) -> Result<Vec<(char, f32)>, String> {
+    let mut terms: Vec<(Vec<char>, Option<f32>)> = Vec::new();
+    for term in spec.split(',') {
+        let (expr, weight) = match split_weight(term) {
+            (expr, Some(w)) => {
+                let weight = w
+                    .trim()
+                    .parse::<f32>()
+                    .ok()
+                    .filter(|w| w.is_finite() && *w > 0.0)
+                    .ok_or_else(|| {
+                        format!("invalid weight `{}` (must be a positive number)", w.trim())
+                    })?;
+                (expr, Some(weight))
+            }
+            (expr, None) => (expr, None),
+        };
+        terms.push((charset_term(expr, default_to_ascii)?, weight));
+    }
+    if !user_ranges.is_empty() {
+        terms.push((
+            build_chars(Charset::NONE, user_ranges, default_to_ascii),
+            None,
+        ));
+    }
+
+    let weighted = terms.iter().any(|(_, w)| w.is_some());
+    let mut out: Vec<(char, f32)> = Vec::new();
+    let mut index: HashMap<char, usize> = HashMap::new();
+    for (glyphs, weight) in terms {
+        let each = weight.unwrap_or(1.0) / glyphs.len() as f32;
+        for c in glyphs {
+            match index.get(&c) {
+                Some(&i) if weighted => out[i].1 += each,
+                Some(_) => {}
+                None => {
+                    index.insert(c, out.len());
+                    out.push((c, if weighted { each } else { 1.0 }));
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Preset names in `--list-charsets` order (the order `s`/`S` cycle through).
+pub fn all_charset_presets() -> &'static [&'static str] {
+    &[
+        "auto",
+        "matrix",
+        "ascii",
+        "extended",
+        "english",
+        "digits",
+        "punc",
+        "binary",
+        "hex",
+        "katakana",
+        "greek",
+        "cyrillic",
+        "hebrew",
+        "blocks",
+        "symbols",
+        "arrows",
+        "retro",
+        "cyberpunk",
+        "hacker",
+        "minimal",
+        "code",
+        "dna",
+        "braille",
+        "runic",
+    ]
+}
+
+/// Maps preset aliases (`bin`, `dec`, ...) to their canonical name.
+pub fn normalize_charset_preset_name(s: &str) -> String {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "bin" | "01" => "binary".to_string(),
+        "dec" | "decimal" => "digits".to_string(),
+        "hexadecimal" => "hex".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Steps `dir` places through [`all_charset_presets`], wrapping around.
+pub fn cycle_charset_preset(current: &str, dir: i32) -> &'static str {
+    let list = all_charset_presets();
+    let Some(pos) = list.iter().position(|&c| c == current) else {
+        return "binary";
+    };
+
+    let n = list.len() as i32;
+    let mut idx = pos as i32 + dir;
+    idx = ((idx % n) + n) % n;
+    list[idx as usize]
+}
+
 fn push_range(out: &mut Vec<char>, start: u32, end: u32) {
     for v in start..=end {
         if let Some(ch) = char::from_u32(v) {
@@ -109,6 +381,8 @@ fn push_range(out: &mut Vec<char>, start: u32, end: u32) {
     }
 }
 
+/// Expands `charset` plus inclusive `user_ranges` into a glyph list without
+/// duplicates, in first-seen order. Never returns an empty list.
 pub fn build_chars(
     mut charset: Charset,
     user_ranges: &[(char, char)],
@@ -192,6 +466,9 @@ pub fn build_chars(
         }
     }
 
+    let mut seen = HashSet::new();
+    out.retain(|&c| seen.insert(c));
+
     if out.is_empty() {
         out.push('0');
         out.push('1');
@@ -210,6 +487,55 @@ mod tests {
         assert_eq!(v, vec!['0', '1']);
     }
 
+    #[test]
+    fn custom_chars_accept_ranges_and_literals() {
+        assert_eq!(
+            parse_char_ranges("U+30A0..U+30A2, u+2588").unwrap(),
+            vec![('゠', 'ア'), ('█', '█')]
+        );
+        assert_eq!(
+            parse_char_ranges("30,39,41,5A").unwrap(),
+            vec![('0', '9'), ('A', 'Z')]
+        );
+        assert!(parse_char_ranges("30,39,41").is_err());
+        let err = parse_char_ranges("U+0000..U+007F").unwrap_err();
+        assert!(err.contains("control character U+0000"), "{}", err);
+
+        let lit = parse_literal_chars("01アイ\n1ウ").unwrap();
+        assert_eq!(lit, vec!['0', '1', 'ア', 'イ', '1', 'ウ']);
+        let err = parse_literal_chars("ab\n c\td").unwrap_err();
+        assert_eq!(err, "control character U+0009 at line 2, column 3");
+
+        let ranges: Vec<(char, char)> = lit.iter().map(|&c| (c, c)).collect();
+        assert_eq!(
+            build_chars(Charset::BINARY, &ranges, false),
+            vec!['0', '1', 'ア', 'イ', 'ウ']
+        );
+    }
+
+    #[test]
+    fn charset_expressions_combine_and_weight_presets() {
+        let plain = weighted_chars_from_str("matrix", &[], false).unwrap();
+        let flat: Vec<char> = plain.iter().map(|&(c, _)| c).collect();
+        assert_eq!(flat, build_chars(Charset::MATRIX, &[], false));
+        assert!(plain.iter().all(|&(_, w)| w == 1.0));
+
+        let set = weighted_chars_from_str("digits+hex-binary", &[], false).unwrap();
+        let set: String = set.iter().map(|&(c, _)| c).collect();
+        assert_eq!(set, "23456789ABCDEF");
+
+        let mix = weighted_chars_from_str("katakana:3, digits:1", &[], false).unwrap();
+        let share = |pred: fn(char) -> bool| -> f32 {
+            mix.iter().filter(|&&(c, _)| pred(c)).map(|&(_, w)| w).sum()
+        };
+        assert!((share(|c| c.is_ascii_digit()) - 1.0).abs() < 1e-4);
+        assert!((share(|c| !c.is_ascii()) - 3.0).abs() < 1e-4);
+
+        assert!(weighted_chars_from_str("digits-digits", &[], false).is_err());
+        assert!(weighted_chars_from_str("digits:0", &[], false).is_err());
+        assert!(weighted_chars_from_str("digits+", &[], false).is_err());
+    }
+
     #[test]
     fn charset_auto_selects_ascii_safe_when_non_utf() {
         let cs = charset_from_str("auto", true).unwrap();
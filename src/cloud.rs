@@ -1,10 +1,12 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! The rain simulation.
+
 use std::time::{Duration, Instant};
 
 use crossterm::style::Color;
 use rand::{
-    distr::{Distribution, Uniform},
+    distr::{weighted::WeightedIndex, Distribution, Uniform},
     rngs::StdRng,
     SeedableRng,
 };
@@ -12,12 +14,18 @@ use rand::{
 use crate::{
     cell::Cell,
     frame::Frame,
-    palette::{build_palette, Palette},
-    runtime::{BoldMode, ColorMode, ColorScheme, ShadingMode},
+    motion::LuminanceLimiter,
+    oklab::Oklab,
+    palette::{
+        build_adjusted_palette, build_palette, color_to_rgb, crossfade, rotate_hue, ColorAdjust,
+        Palette,
+    },
+    runtime::{BoldMode, ColorMode, ColorScheme, ShadingMode, SmoothShading},
 };
 
 use crate::droplet::Droplet;
 
+/// Where a glyph sits within its droplet.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum CharLoc {
     Middle,
@@ -25,21 +33,143 @@ pub enum CharLoc {
     Head,
 }
 
+/// 4x4 Bayer matrix: the order in which cells of a block switch to the
+/// next color as a gradient position moves between two levels.
+const BAYER4: [u8; 16] = [0, 8, 2, 10, 12, 4, 14, 6, 3, 11, 1, 9, 15, 7, 13, 5];
+
+/// Distinct colors of `palette` for dithering, each at the center of its run
+/// of equal entries. Quantizing a long gradient repeats colors; dithering
+/// between run centers keeps each color where the gradient put it.
+pub fn dither_levels(palette: &[Color]) -> Vec<(f32, Color)> {
+    let mut levels: Vec<(f32, Color)> = Vec::new();
+    let mut start = 0;
+    for i in 1..=palette.len() {
+        if i == palette.len() || palette[i] != palette[start] {
+            levels.push(((start + i - 1) as f32 / 2.0, palette[start]));
+            start = i;
+        }
+    }
+    levels
+}
+
+/// Color at palette position `pos` for the cell at `col`/`line`: one of the
+/// two levels around `pos`, chosen by the Bayer threshold of the cell.
+fn dither_pick(levels: &[(f32, Color)], pos: f32, col: u16, line: u16) -> Option<Color> {
+    let Some(hi) = levels.iter().position(|&(center, _)| center > pos) else {
+        return levels.last().map(|&(_, c)| c);
+    };
+    if hi == 0 {
+        return Some(levels[0].1);
+    }
+    let (c0, lo_color) = levels[hi - 1];
+    let (c1, hi_color) = levels[hi];
+    let frac = (pos - c0) / (c1 - c0);
+    let threshold = (BAYER4[(line as usize % 4) * 4 + col as usize % 4] as f32 + 0.5) / 16.0;
+    Some(if frac > threshold { hi_color } else { lo_color })
+}
+
+/// Palette position (0 = tail color, `last` = head color) of a body cell
+/// `dist` lines behind the head, `head_frac` of a line further on. Brightness
+/// falls off with the square of the distance; the two cells nearest the head
+/// get one extra step.
+fn distance_shade(dist: f32, length: u16, last: f32) -> f32 {
+    let len = length.max(1) as f32;
+    let inv = (len - dist).clamp(0.0, len);
+    let bump = 1.0 - (dist - 1.0).clamp(0.0, 1.0);
+    (inv * inv * last / (len * len) + bump).min(last)
+}
+
+/// Seconds for one full turn of the hue wheel in [`ShadingMode::Time`].
+const TIME_HUE_PERIOD_SECS: f32 = 8.0;
+
+/// With reduced motion, rain speed and spawn rate are scaled by this...
+const REDUCED_MOTION_SLOWDOWN: f32 = 0.5;
+/// ...and no cell changes brightness more than this many times a second
+/// (the WCAG flash threshold is three).
+const REDUCED_MOTION_MAX_CPS: f32 = 3.0;
+/// Shortest theme change with reduced motion; instant swaps become fades.
+const REDUCED_MOTION_FADE: Duration = Duration::from_millis(1500);
+
+/// Palette colors in OKLab, for blending between entries.
+fn shade_lab(palette: &[Color]) -> Vec<Oklab> {
+    palette
+        .iter()
+        .filter_map(|&c| color_to_rgb(c).map(Oklab::from_rgb))
+        .collect()
+}
+
+/// Color at fractional palette position `pos`.
+fn blend(lab: &[Oklab], pos: f32) -> Option<Color> {
+    let last = lab.len().checked_sub(1)?;
+    let i = (pos.max(0.0).floor() as usize).min(last.saturating_sub(1));
+    let next = lab.get(i + 1).copied().unwrap_or(lab[i]);
+    let (r, g, b) = lab[i].lerp(next, (pos - i as f32).clamp(0.0, 1.0)).to_rgb();
+    Some(Color::Rgb { r, g, b })
+}
+
+/// One theme of a `mix:` theme, with the tables dithering and smooth shading
+/// need.
+#[derive(Clone, Debug)]
+pub struct MixPalette {
+    pub colors: Vec<Color>,
+    pub dither_levels: Vec<(f32, Color)>,
+    pub shade_lab: Vec<Oklab>,
+}
+
+/// Palettes for each part of a [`ColorScheme::Mix`], empty for other themes.
+fn mix_palettes(
+    scheme: &ColorScheme,
+    mode: ColorMode,
+    default_background: bool,
+    adjust: ColorAdjust,
+) -> Vec<MixPalette> {
+    let ColorScheme::Mix(parts) = scheme else {
+        return Vec::new();
+    };
+    parts
+        .iter()
+        .map(|part| {
+            let colors = build_adjusted_palette(part, mode, default_background, adjust).colors;
+            MixPalette {
+                dither_levels: dither_levels(&colors),
+                shade_lab: shade_lab(&colors),
+                colors,
+            }
+        })
+        .collect()
+}
+
+/// Read-only view of the [`Cloud`] state that [`Droplet::draw`] needs.
 pub struct DrawCtx<'a> {
     pub lines: u16,
+    pub cols: u16,
     pub full_width: bool,
     pub shading_distance: bool,
+    pub shading_mode: ShadingMode,
+    /// Start of the [`ShadingMode::Time`] cycle.
+    pub shade_start_time: Instant,
     pub bg: Option<Color>,
 
     pub color_mode: ColorMode,
     pub bold_mode: BoldMode,
     pub glitchy: bool,
+    /// Glitched cells swap glyphs without flashing brighter or dimmer.
+    pub reduced_motion: bool,
 
     pub last_glitch_time: Instant,
     pub next_glitch_time: Instant,
 
     pub palette_colors: &'a [Color],
+    /// Set when ordered dithering is on: see [`dither_levels`].
+    pub dither_levels: Option<&'a [(f32, Color)]>,
+    /// Set for continuous truecolor shading: the palette in OKLab.
+    pub shade_lab: Option<&'a [Oklab]>,
+    /// Whether continuous shading follows the head between whole cells.
+    pub shade_over_time: bool,
     pub color_map: &'a [u8],
+    /// The themes of a `mix:` theme, and which one each column uses.
+    pub mix_palettes: &'a [MixPalette],
+    pub column_palette: &'a [u8],
     pub glitch_map: &'a [bool],
     pub char_pool: &'a [char],
 }
@@ -87,6 +217,68 @@ impl DrawCtx<'_> {
         self.glitch_map.get(idx).copied().unwrap_or(false)
     }
 
+    /// Whether body colors change while a droplet falls, so cells already
+    /// drawn must be redrawn every frame.
+    pub fn recolors_body(&self) -> bool {
+        matches!(
+            self.shading_mode,
+            ShadingMode::DistanceFromHead
+                | ShadingMode::Column
+                | ShadingMode::Time
+                | ShadingMode::Age
+        )
+    }
+
+    /// Palette of column `col` (it differs per column with a `mix:` theme),
+    /// with its shading tables when those are enabled.
+    #[allow(clippy::type_complexity)]
+    fn palette_for(&self, col: u16) -> (&[Color], Option<&[Oklab]>, Option<&[(f32, Color)]>) {
+        let mixed = self
+            .column_palette
+            .get(col as usize)
+            .and_then(|&i| self.mix_palettes.get(i as usize));
+        match mixed {
+            Some(p) => (
+                &p.colors,
+                self.shade_lab.map(|_| p.shade_lab.as_slice()),
+                self.dither_levels.map(|_| p.dither_levels.as_slice()),
+            ),
+            None => (self.palette_colors, self.shade_lab, self.dither_levels),
+        }
+    }
+
+    /// Palette position (0 = tail color, `last` = head color) for the
+    /// row and age shading modes.
+    fn mode_shade(&self, line: u16, age: f32, last: f32) -> f32 {
+        let frac = |i: u16, n: u16| {
+            if n > 1 {
+                i as f32 / (n - 1) as f32
+            } else {
+                1.0
+            }
+        };
+        let t = match self.shading_mode {
+            ShadingMode::Row => frac(line, self.lines),
+            ShadingMode::Age => 1.0 - age,
+            _ => 0.0,
+        };
+        t.clamp(0.0, 1.0) * last
+    }
+
+    /// Degrees the hue has turned by at `now` in [`ShadingMode::Time`].
+    fn time_hue(&self, now: Instant) -> f32 {
+        let secs = now
+            .saturating_duration_since(self.shade_start_time)
+            .as_secs_f32();
+        (secs / TIME_HUE_PERIOD_SECS).fract() * 360.0
+    }
+
+    /// Degrees the hue is turned by in column `col` in
+    /// [`ShadingMode::Column`]: once round the wheel across the screen.
+    fn column_hue(&self, col: u16) -> f32 {
+        col as f32 / self.cols.max(1) as f32 * 360.0
+    }
+
     pub fn get_char(&self, line: u16, char_pool_idx: u16) -> char {
         let len = self.char_pool.len().max(1);
         let idx = ((char_pool_idx as usize) + (line as usize)) % len;
@@ -102,18 +294,32 @@ impl DrawCtx<'_> {
         loc: CharLoc,
         now: Instant,
         head_put_line: u16,
+        head_frac: f32,
         length: u16,
+        age: f32,
     ) -> (Option<Color>, bool) {
         let mut bold = false;
         if self.bold_mode == BoldMode::Random {
             bold = (((line as u32) ^ (val as u32)) % 2) == 1;
         }
 
+        let (palette, shade_lab, dither_levels) = self.palette_for(col);
         let idx = col as usize * self.lines as usize + line as usize;
         let mut color_idx = self.color_map.get(idx).copied().unwrap_or(0) as i32;
-
-        if self.shading_distance {
-            let last = self.palette_colors.len().saturating_sub(1) as u64;
+        if palette.len() != self.palette_colors.len() && self.palette_colors.len() > 1 {
+            // `color_map` indexes the main palette; keep the same relative shade.
+            let scale =
+                palette.len().saturating_sub(1) as f32 / (self.palette_colors.len() - 1) as f32;
+            color_idx = (color_idx as f32 * scale).round() as i32;
+        }
+        let mut shade_pos: Option<f32> = None;
+
+        let smooth = shade_lab.is_some() || dither_levels.is_some();
+        if matches!(
+            self.shading_mode,
+            ShadingMode::DistanceFromHead | ShadingMode::Column | ShadingMode::Time
+        ) {
+            let last = palette.len().saturating_sub(1) as u64;
             let dist = head_put_line.saturating_sub(line) as u64;
             let len = length.max(1) as u64;
 
@@ -129,21 +335,41 @@ impl DrawCtx<'_> {
             if dist <= 1 {
                 v = (v + 1).min(last);
             }
+            if smooth {
+                let ahead = if self.shade_over_time && shade_lab.is_some() {
+                    head_frac
+                } else {
+                    0.0
+                };
+                shade_pos = Some(distance_shade(dist as f32 + ahead, length, last as f32));
+            }
 
             color_idx = v as i32;
+        } else if self.shading_mode != ShadingMode::Random {
+            let last = palette.len().saturating_sub(1) as f32;
+            let pos = self.mode_shade(line, age, last);
+            color_idx = pos.round() as i32;
+            if smooth {
+                shade_pos = Some(pos);
+            }
         }
 
-        if self.glitchy && self.glitch_map.get(idx).copied().unwrap_or(false) {
+        if self.glitchy
+            && !self.reduced_motion
+            && self.glitch_map.get(idx).copied().unwrap_or(false)
+        {
             if self.is_bright(now) {
                 color_idx += 1;
                 bold = true;
+                shade_pos = None;
             } else if self.is_dim(now) {
                 color_idx -= 1;
                 bold = false;
+                shade_pos = None;
             }
         }
 
-        let last = self.palette_colors.len().saturating_sub(1) as i32;
+        let last = palette.len().saturating_sub(1) as i32;
         match loc {
             CharLoc::Tail => {
                 color_idx = 0;
@@ -164,10 +390,28 @@ impl DrawCtx<'_> {
             BoldMode::Random => {}
         }
 
+        let shaded = match (loc, shade_pos) {
+            (CharLoc::Middle, Some(pos)) => match (shade_lab, dither_levels) {
+                (Some(lab), _) => blend(lab, pos),
+                (None, Some(levels)) => dither_pick(levels, pos, col, line),
+                (None, None) => None,
+            },
+            _ => None,
+        };
+
         let fg = if self.color_mode == ColorMode::Mono {
             None
         } else {
-            self.palette_colors.get(color_idx as usize).copied()
+            shaded.or_else(|| palette.get(color_idx as usize).copied())
+        };
+        let fg = match (fg, self.shading_mode) {
+            (Some(c), ShadingMode::Time) => {
+                Some(rotate_hue(c, self.time_hue(now), self.color_mode))
+            }
+            (Some(c), ShadingMode::Column) => {
+                Some(rotate_hue(c, self.column_hue(col), self.color_mode))
+            }
+            (fg, _) => fg,
         };
 
         (fg, bold)
@@ -188,52 +432,166 @@ struct MsgChr {
     val: char,
 }
 
+/// A theme change in progress, see [`Cloud::fade_to_color_scheme`].
+#[derive(Clone, Debug)]
+struct PaletteFade {
+    from: Vec<Color>,
+    to: Vec<Color>,
+    start: Instant,
+    duration: Duration,
+}
+
+/// Options a [`Cloud`] is created with; everything else has a setter.
+///
+/// ```
+/// use cosmostrix::{Cloud, ColorScheme};
+///
+/// let cloud = Cloud::builder().color_scheme(ColorScheme::Fire).seed(7).build();
+/// assert_eq!(cloud.seed(), 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CloudBuilder {
+    color_mode: ColorMode,
+    full_width: bool,
+    shading_mode: ShadingMode,
+    bold_mode: BoldMode,
+    async_mode: bool,
+    default_background: bool,
+    color_scheme: ColorScheme,
+    seed: u64,
+    glitchy: bool,
+}
+
+impl Default for CloudBuilder {
+    fn default() -> Self {
+        Self {
+            color_mode: ColorMode::TrueColor,
+            full_width: false,
+            shading_mode: ShadingMode::DistanceFromHead,
+            bold_mode: BoldMode::Random,
+            async_mode: false,
+            default_background: false,
+            color_scheme: ColorScheme::Green,
+            seed: 0,
+            glitchy: true,
+        }
+    }
+}
+
+impl CloudBuilder {
+    /// Colors the terminal can show (default: truecolor).
+    pub fn color_mode(mut self, mode: ColorMode) -> Self {
+        self.color_mode = mode;
+        self
+    }
+
+    /// Droplets only in every other column, for glyphs two cells wide.
+    pub fn full_width(mut self, on: bool) -> Self {
+        self.full_width = on;
+        self
+    }
+
+    /// How droplet bodies are colored (default: distance from head).
+    pub fn shading_mode(mut self, mode: ShadingMode) -> Self {
+        self.shading_mode = mode;
+        self
+    }
+
+    /// Which glyphs are bold (default: random).
+    pub fn bold_mode(mut self, mode: BoldMode) -> Self {
+        self.bold_mode = mode;
+        self
+    }
+
+    /// Columns fall at different speeds.
+    pub fn async_mode(mut self, on: bool) -> Self {
+        self.async_mode = on;
+        self
+    }
+
+    /// Leave the background to the terminal instead of painting it black.
+    pub fn default_background(mut self, on: bool) -> Self {
+        self.default_background = on;
+        self
+    }
+
+    /// Theme (default: green).
+    pub fn color_scheme(mut self, scheme: ColorScheme) -> Self {
+        self.color_scheme = scheme;
+        self
+    }
+
+    /// RNG seed (default: 0). The same seed, size, options and timestamps
+    /// always produce the same frames.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Glitching glyphs (default: on).
+    pub fn glitchy(mut self, on: bool) -> Self {
+        self.glitchy = on;
+        self
+    }
+
+    pub fn build(self) -> Cloud {
+        Cloud::new(self)
+    }
+}
+
+/// The whole animation: droplet pool, glyph pools, palette and timing.
+///
+/// Configure it with the setters, size it with [`Cloud::reset`], then call
+/// [`Cloud::rain`] (or [`Cloud::rain_at`] with your own clock) once per frame.
 pub struct Cloud {
-    pub lines: u16,
-    pub cols: u16,
+    pub(crate) lines: u16,
+    pub(crate) cols: u16,
 
-    pub palette: Palette,
-    pub color_mode: ColorMode,
+    pub(crate) palette: Palette,
+    pub(crate) color_mode: ColorMode,
 
-    pub full_width: bool,
-    pub shading_distance: bool,
-    pub bold_mode: BoldMode,
+    pub(crate) full_width: bool,
+    pub(crate) shading_distance: bool,
+    pub(crate) bold_mode: BoldMode,
 
-    pub async_mode: bool,
-    pub raining: bool,
-    pub pause: bool,
+    pub(crate) async_mode: bool,
+    pub(crate) raining: bool,
+    pub(crate) pause: bool,
 
-    pub droplet_density: f32,
-    pub droplets_per_sec: f32,
-    pub chars_per_sec: f32,
+    pub(crate) droplet_density: f32,
+    pub(crate) droplets_per_sec: f32,
+    pub(crate) chars_per_sec: f32,
 
-    pub glitchy: bool,
-    pub glitch_pct: f32,
-    pub glitch_low_ms: u16,
-    pub glitch_high_ms: u16,
+    pub(crate) glitchy: bool,
+    pub(crate) glitch_pct: f32,
+    pub(crate) glitch_low_ms: u16,
+    pub(crate) glitch_high_ms: u16,
 
-    pub short_pct: f32,
-    pub die_early_pct: f32,
-    pub linger_low_ms: u16,
-    pub linger_high_ms: u16,
+    pub(crate) short_pct: f32,
+    pub(crate) die_early_pct: f32,
+    pub(crate) linger_low_ms: u16,
+    pub(crate) linger_high_ms: u16,
 
-    pub max_droplets_per_column: u8,
+    pub(crate) max_droplets_per_column: u8,
 
     droplets: Vec<Droplet>,
     num_droplets: usize,
     spawn_scan_idx: usize,
 
-    chars: Vec<char>,
+    pub(crate) chars: Vec<char>,
     char_pool: Vec<char>,
     glitch_pool: Vec<char>,
     glitch_pool_idx: usize,
 
     glitch_map: Vec<bool>,
     color_map: Vec<u8>,
+    mix: Vec<MixPalette>,
+    column_palette: Vec<u8>,
 
     col_stat: Vec<ColumnStatus>,
 
     mt: StdRng,
+    seed: u64,
 
     rand_chance: Uniform<f32>,
     rand_line: Uniform<u16>,
@@ -256,32 +614,52 @@ pub struct Cloud {
     max_sim_delta: Duration,
 
     shading_mode: ShadingMode,
+    shade_start_time: Instant,
 
     message: Vec<MsgChr>,
     message_text: Option<String>,
     message_border: bool,
     color_scheme: ColorScheme,
     default_background: bool,
+    dither: bool,
+    dither_levels: Vec<(f32, Color)>,
+    smooth_shading: SmoothShading,
+    shade_lab: Vec<Oklab>,
+    fade: Option<PaletteFade>,
+    color_adjust: ColorAdjust,
+    reduced_motion: bool,
+    /// Time of the last [`Cloud::rain_at`]; theme changes made between
+    /// frames fade from there, which keeps seeded runs reproducible.
+    last_rain: Option<Instant>,
+    /// With reduced motion, the rain is drawn here and eased onto the screen.
+    limiter: Option<LuminanceLimiter>,
 }
 
 impl Cloud {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        color_mode: ColorMode,
-        full_width: bool,
-        shading_mode: ShadingMode,
-        bold_mode: BoldMode,
-        async_mode: bool,
-        default_background: bool,
-        color_scheme: ColorScheme,
-    ) -> Self {
+    /// Starts a [`CloudBuilder`] with the default options.
+    pub fn builder() -> CloudBuilder {
+        CloudBuilder::default()
+    }
+
+    fn new(opts: CloudBuilder) -> Self {
+        let CloudBuilder {
+            color_mode,
+            full_width,
+            shading_mode,
+            bold_mode,
+            async_mode,
+            default_background,
+            color_scheme,
+            seed,
+            glitchy,
+        } = opts;
         let now = Instant::now();
-        let mt = StdRng::seed_from_u64(0x1234567);
+        let mt = StdRng::seed_from_u64(seed);
 
         Self {
             lines: 25,
             cols: 80,
-            palette: build_palette(color_scheme, color_mode, default_background),
+            palette: build_palette(&color_scheme, color_mode, default_background),
             color_mode,
             full_width,
             shading_distance: matches!(shading_mode, ShadingMode::DistanceFromHead),
@@ -292,7 +670,7 @@ impl Cloud {
             droplet_density: 1.0,
             droplets_per_sec: 5.0,
             chars_per_sec: 8.0,
-            glitchy: true,
+            glitchy,
             glitch_pct: 0.1,
             glitch_low_ms: 300,
             glitch_high_ms: 400,
@@ -310,8 +688,16 @@ impl Cloud {
             glitch_pool_idx: 0,
             glitch_map: Vec::new(),
             color_map: Vec::new(),
+            mix: mix_palettes(
+                &color_scheme,
+                color_mode,
+                default_background,
+                ColorAdjust::default(),
+            ),
+            column_palette: Vec::new(),
             col_stat: Vec::new(),
             mt,
+            seed,
             rand_chance: Uniform::new(0.0, 1.0).expect("valid range"),
             rand_line: Uniform::new_inclusive(0, 23).expect("valid range"),
             rand_cpidx: Uniform::new_inclusive(0, 2047).expect("valid range"),
@@ -329,14 +715,25 @@ impl Cloud {
             perf_pressure: 0.0,
             max_sim_delta: Duration::from_millis(0),
             shading_mode,
+            shade_start_time: now,
             message: Vec::new(),
             message_text: None,
             message_border: true,
             color_scheme,
             default_background,
+            dither: false,
+            dither_levels: Vec::new(),
+            smooth_shading: SmoothShading::Off,
+            shade_lab: Vec::new(),
+            fade: None,
+            color_adjust: ColorAdjust::default(),
+            reduced_motion: false,
+            last_rain: None,
+            limiter: None,
         }
     }
 
+    /// Shows `msg` in a centered box over the rain.
     pub fn set_message(&mut self, msg: &str) {
         self.message_text = Some(msg.to_string());
         self.reset_message();
@@ -351,15 +748,223 @@ impl Cloud {
         }
     }
 
+    /// Switches theme and redraws everything; with reduced motion the new
+    /// colors fade in from the last frame instead.
     pub fn set_color_scheme(&mut self, scheme: ColorScheme) {
+        match self.last_rain {
+            Some(now) if self.reduced_motion => {
+                self.fade_to_color_scheme(scheme, REDUCED_MOTION_FADE, now)
+            }
+            _ => self.apply_color_scheme(scheme),
+        }
+    }
+
+    fn apply_color_scheme(&mut self, scheme: ColorScheme) {
+        self.palette = build_adjusted_palette(
+            &scheme,
+            self.color_mode,
+            self.default_background,
+            self.color_adjust,
+        );
+        self.dither_levels = dither_levels(&self.palette.colors);
+        self.shade_lab = shade_lab(&self.palette.colors);
+        self.mix = mix_palettes(
+            &scheme,
+            self.color_mode,
+            self.default_background,
+            self.color_adjust,
+        );
         self.color_scheme = scheme;
-        self.palette = build_palette(scheme, self.color_mode, self.default_background);
+        self.fade = None;
         self.fill_color_map();
         self.force_draw_everything = true;
     }
 
-    pub fn color_scheme(&self) -> ColorScheme {
-        self.color_scheme
+    /// Switches theme, blending every color from the current palette to the
+    /// new one over `duration` starting at `now` (instantly when `duration`
+    /// is zero or either theme is a `mix:`). The background switches right
+    /// away. Reduced motion stretches short fades to a minimum length.
+    pub fn fade_to_color_scheme(&mut self, scheme: ColorScheme, duration: Duration, now: Instant) {
+        let duration = if self.reduced_motion {
+            duration.max(REDUCED_MOTION_FADE)
+        } else {
+            duration
+        };
+        let from = self.palette.colors.clone();
+        let was_mixed = !self.mix.is_empty();
+        self.apply_color_scheme(scheme);
+        if duration.is_zero()
+            || self.color_mode == ColorMode::Mono
+            || was_mixed
+            || !self.mix.is_empty()
+        {
+            return;
+        }
+        self.fade = Some(PaletteFade {
+            from,
+            to: self.palette.colors.clone(),
+            start: now,
+            duration,
+        });
+        self.step_fade(now);
+    }
+
+    /// Every color the theme draws with: the palette plus, for a `mix:`
+    /// theme, the palettes of its other themes.
+    pub fn theme_palette(&self) -> Palette {
+        let mut p = self.palette.clone();
+        for m in &self.mix {
+            p.colors.extend(m.colors.iter().copied());
+        }
+        p
+    }
+
+    /// Whether a [`Cloud::fade_to_color_scheme`] is still in progress.
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+
+    /// Moves a running crossfade to `now`. Returns true if the palette
+    /// changed, so every visible cell needs recoloring.
+    fn step_fade(&mut self, now: Instant) -> bool {
+        let Some(fade) = &self.fade else {
+            return false;
+        };
+        let t =
+            now.saturating_duration_since(fade.start).as_secs_f32() / fade.duration.as_secs_f32();
+        let colors = if t >= 1.0 {
+            let to = fade.to.clone();
+            self.fade = None;
+            to
+        } else {
+            crossfade(&fade.from, &fade.to, t, self.color_mode)
+        };
+        if colors == self.palette.colors {
+            return false;
+        }
+        self.palette.colors = colors;
+        self.dither_levels = dither_levels(&self.palette.colors);
+        self.shade_lab = shade_lab(&self.palette.colors);
+        true
+    }
+
+    pub fn color_adjust(&self) -> ColorAdjust {
+        self.color_adjust
+    }
+
+    /// Sets brightness, contrast, saturation and hue shift (clamped to their
+    /// ranges) and rebuilds the palette.
+    pub fn set_color_adjust(&mut self, adjust: ColorAdjust) {
+        self.color_adjust = adjust.clamped();
+        self.set_color_scheme(self.color_scheme.clone());
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.reduced_motion
+    }
+
+    /// Photosensitivity-safe rendering: slower rain and fewer droplets, at
+    /// most three brightness changes per cell a second, no glitch flashes,
+    /// and theme changes that fade instead of switching. Outside mono mode
+    /// every cell's lightness is also rate-limited, so redraws, resets and shading changes fade too.
+    pub fn set_reduced_motion(&mut self, on: bool) {
+        self.reduced_motion = on;
+        self.limiter = None;
+        self.recalc_droplets_per_sec();
+        self.update_droplet_speeds();
+        self.force_draw_everything = true;
+    }
+
+    /// Speed droplets actually fall at, see [`Cloud::set_reduced_motion`].
+    fn motion_chars_per_sec(&self) -> f32 {
+        if self.reduced_motion {
+            (self.chars_per_sec * REDUCED_MOTION_SLOWDOWN).min(REDUCED_MOTION_MAX_CPS)
+        } else {
+            self.chars_per_sec
+        }
+    }
+
+    /// Ordered (Bayer) dithering between neighboring palette colors for
+    /// distance-from-head shading. Only has an effect in 16- and 256-color
+    /// modes, where quantizing turns long gradients into bands.
+    pub fn set_dither(&mut self, on: bool) {
+        self.dither = on;
+        self.dither_levels = dither_levels(&self.palette.colors);
+        self.force_draw_everything = true;
+    }
+
+    /// Continuous shading between palette colors for distance-from-head
+    /// shading in truecolor mode; lower color modes keep one palette color
+    /// per cell.
+    pub fn set_smooth_shading(&mut self, mode: SmoothShading) {
+        self.smooth_shading = mode;
+        self.shade_lab = shade_lab(&self.palette.colors);
+        self.force_draw_everything = true;
+    }
+
+    pub fn color_scheme(&self) -> &ColorScheme {
+        &self.color_scheme
+    }
+
+    /// Seed the cloud's RNG was created with.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Current palette, including any crossfade in progress.
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn color_mode(&self) -> ColorMode {
+        self.color_mode
+    }
+
+    /// Size set by the last [`Cloud::reset`], as `(cols, lines)`.
+    pub fn size(&self) -> (u16, u16) {
+        (self.cols, self.lines)
+    }
+
+    /// False once [`Cloud::stop`] was called (the run loop then returns).
+    pub fn is_raining(&self) -> bool {
+        self.raining
+    }
+
+    pub fn stop(&mut self) {
+        self.raining = false;
+    }
+
+    pub fn full_width(&self) -> bool {
+        self.full_width
+    }
+
+    /// Switches between one glyph per cell and double-width glyphs on every
+    /// other column. Call [`Cloud::reset`] afterwards so droplets re-spawn on
+    /// the right columns.
+    pub fn set_full_width(&mut self, on: bool) {
+        self.full_width = on;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.pause
+    }
+
+    pub fn chars_per_sec(&self) -> f32 {
+        self.chars_per_sec
+    }
+
+    pub fn droplet_density(&self) -> f32 {
+        self.droplet_density
+    }
+
+    /// Share (0-1) of droplets that are short.
+    pub fn set_short_pct(&mut self, pct: f32) {
+        self.short_pct = pct;
+    }
+
+    /// Share (0-1) of droplets that stop before reaching the bottom.
+    pub fn set_die_early_pct(&mut self, pct: f32) {
+        self.die_early_pct = pct;
     }
 
     pub fn set_async(&mut self, on: bool) {
@@ -368,6 +973,7 @@ impl Cloud {
         self.update_droplet_speeds();
     }
 
+    /// Sets rain speed in characters per second.
     pub fn set_chars_per_sec(&mut self, cps: f32) {
         self.chars_per_sec = cps;
         self.recalc_droplets_per_sec();
@@ -375,6 +981,7 @@ impl Cloud {
         self.update_droplet_speeds();
     }
 
+    /// Sets how many droplets spawn relative to the screen size.
     pub fn set_droplet_density(&mut self, density: f32) {
         self.droplet_density = density;
         self.recalc_droplets_per_sec();
@@ -438,6 +1045,10 @@ impl Cloud {
         } else if let Some(pt) = self.pause_time.take() {
             let elapsed = Instant::now().saturating_duration_since(pt);
             self.last_spawn_time += elapsed;
+            self.shade_start_time += elapsed;
+            if let Some(fade) = self.fade.as_mut() {
+                fade.start += elapsed;
+            }
             for d in &mut self.droplets {
                 if d.is_alive {
                     d.increment_time(elapsed);
@@ -446,7 +1057,13 @@ impl Cloud {
         }
     }
 
+    /// Resizes to `cols` x `lines` and restarts the animation.
     pub fn reset(&mut self, cols: u16, lines: u16) {
+        self.reset_at(cols, lines, Instant::now());
+    }
+
+    /// Like [`Cloud::reset`], with `now` as the animation start time.
+    pub fn reset_at(&mut self, cols: u16, lines: u16, now: Instant) {
         self.cols = cols;
         self.lines = lines;
 
@@ -483,17 +1100,30 @@ impl Cloud {
             self.reset_message();
         }
 
-        let now = Instant::now();
         self.last_glitch_time = now;
         self.next_glitch_time =
             now + Duration::from_millis(self.rand_glitch_ms.sample(&mut self.mt) as u64);
         self.last_spawn_time = now;
+        self.shade_start_time = now;
         self.spawn_remainder = 0.0;
         self.force_draw_everything = true;
     }
 
+    /// Sets the glyphs droplets are drawn with, all equally likely.
     pub fn init_chars(&mut self, chars: Vec<char>) {
-        self.chars = chars;
+        self.init_weighted_chars(chars.into_iter().map(|c| (c, 1.0)).collect());
+    }
+
+    /// Sets the glyphs droplets are drawn with, each picked in proportion to
+    /// its weight (see [`crate::charset::weighted_chars_from_str`]).
+    pub fn init_weighted_chars(&mut self, chars: Vec<(char, f32)>) {
+        let (glyphs, weights): (Vec<char>, Vec<f32>) = chars.into_iter().unzip();
+        let weighted = if weights.windows(2).any(|w| w[0] != w[1]) {
+            WeightedIndex::new(&weights).ok()
+        } else {
+            None
+        };
+        self.chars = glyphs;
         if self.chars.is_empty() {
             self.chars.push('0');
             self.chars.push('1');
@@ -505,12 +1135,16 @@ impl Cloud {
 
         let dist = Uniform::new_inclusive(0usize, self.chars.len().saturating_sub(1))
             .expect("valid range");
+        let pick = |mt: &mut StdRng| match &weighted {
+            Some(w) => w.sample(mt),
+            None => dist.sample(mt),
+        };
         for i in 0..self.char_pool.len() {
-            let idx = dist.sample(&mut self.mt);
+            let idx = pick(&mut self.mt);
             self.char_pool[i] = self.chars[idx];
         }
         for i in 0..self.glitch_pool.len() {
-            let idx = dist.sample(&mut self.mt);
+            let idx = pick(&mut self.mt);
             self.glitch_pool[i] = self.chars[idx];
         }
     }
@@ -520,12 +1154,15 @@ impl Cloud {
             self.droplets_per_sec = 0.0;
             return;
         }
-        let droplet_seconds = (self.lines as f32) / self.chars_per_sec.max(0.001);
+        let droplet_seconds = (self.lines as f32) / self.motion_chars_per_sec().max(0.001);
         if droplet_seconds <= 0.0 {
             self.droplets_per_sec = 0.0;
             return;
         }
-        let dps = (self.cols as f32) * self.droplet_density / droplet_seconds;
+        let mut dps = (self.cols as f32) * self.droplet_density / droplet_seconds;
+        if self.reduced_motion {
+            dps *= REDUCED_MOTION_SLOWDOWN;
+        }
         self.droplets_per_sec = if dps.is_finite() { dps.max(0.0) } else { 0.0 };
     }
 
@@ -556,6 +1193,12 @@ impl Cloud {
         for v in &mut self.color_map {
             *v = dist.sample(&mut self.mt);
         }
+
+        self.column_palette.clear();
+        if !self.mix.is_empty() {
+            let pick = Uniform::new(0, self.mix.len() as u8).expect("valid range");
+            self.column_palette = (0..self.cols).map(|_| pick.sample(&mut self.mt)).collect();
+        }
     }
 
     pub fn set_column_spawn(&mut self, col: u16, b: bool) {
@@ -575,12 +1218,13 @@ impl Cloud {
     }
 
     fn update_droplet_speeds(&mut self) {
+        let cps = self.motion_chars_per_sec();
         for d in &mut self.droplets {
             if !d.is_alive {
                 continue;
             }
             if let Some(cs) = self.col_stat.get(d.bound_col as usize) {
-                d.chars_per_sec = cs.max_speed_pct * self.chars_per_sec;
+                d.chars_per_sec = cs.max_speed_pct * cps;
             }
         }
     }
@@ -638,7 +1282,7 @@ impl Cloud {
             .get(col as usize)
             .map(|cs| cs.max_speed_pct)
             .unwrap_or(1.0)
-            * self.chars_per_sec;
+            * self.motion_chars_per_sec();
 
         d.bound_col = col;
         d.end_line = end_line;
@@ -743,6 +1387,10 @@ impl Cloud {
         self.force_draw_everything = true;
     }
 
+    pub fn shading_mode(&self) -> ShadingMode {
+        self.shading_mode
+    }
+
     pub fn set_shading_mode(&mut self, sm: ShadingMode) {
         self.shading_mode = sm;
         self.shading_distance = matches!(sm, ShadingMode::DistanceFromHead);
@@ -912,17 +1560,39 @@ impl Cloud {
         }
     }
 
+    /// Advances to the current time and draws into `frame`.
     pub fn rain(&mut self, frame: &mut Frame) {
         self.rain_at(frame, Instant::now());
     }
 
+    /// Advances the simulation to `now` and draws changed cells into `frame`.
     pub fn rain_at(&mut self, frame: &mut Frame, now: Instant) {
         if self.pause {
             return;
         }
+        let since = self
+            .last_rain
+            .map_or(Duration::ZERO, |t| now.saturating_duration_since(t));
+        self.last_rain = Some(now);
 
+        if !self.reduced_motion || self.color_mode == ColorMode::Mono {
+            self.step(frame, now);
+            return;
+        }
+        let mut limiter = match self.limiter.take() {
+            Some(l) if l.fits(frame) => l,
+            _ => LuminanceLimiter::new(frame),
+        };
+        self.step(limiter.target(), now);
+        limiter.apply(frame, since, self.color_mode);
+        self.limiter = Some(limiter);
+    }
+
+    /// Moves droplets to `now` and draws them into `frame`.
+    fn step(&mut self, frame: &mut Frame, now: Instant) {
         let spawn_scale = (1.0 - (0.75 * self.perf_pressure)).clamp(0.25, 1.0);
         self.spawn_droplets(now, spawn_scale);
+        let recolor = self.step_fade(now);
 
         if self.force_draw_everything {
             frame.clear_with_bg(self.palette.bg);
@@ -984,19 +1654,40 @@ impl Cloud {
         }
 
         // Draw pass (split-borrows via DrawCtx)
-        let draw_everything = self.force_draw_everything || time_for_glitch;
+        let draw_everything = self.force_draw_everything || time_for_glitch || recolor;
         let ctx = DrawCtx {
             lines: self.lines,
+            cols: self.cols,
             full_width: self.full_width,
             shading_distance: self.shading_distance,
+            shading_mode: self.shading_mode,
+            shade_start_time: self.shade_start_time,
             bg: self.palette.bg,
             color_mode: self.color_mode,
             bold_mode: self.bold_mode,
             glitchy: self.glitchy,
+            reduced_motion: self.reduced_motion,
             last_glitch_time: self.last_glitch_time,
             next_glitch_time: self.next_glitch_time,
             palette_colors: &self.palette.colors,
+            dither_levels: match self.color_mode {
+                ColorMode::Color16 | ColorMode::Color256 if self.dither => {
+                    Some(&self.dither_levels)
+                }
+                _ => None,
+            },
+            shade_lab: match self.smooth_shading {
+                SmoothShading::Length | SmoothShading::Time
+                    if self.color_mode == ColorMode::TrueColor =>
+                {
+                    Some(&self.shade_lab)
+                }
+                _ => None,
+            },
+            shade_over_time: self.smooth_shading == SmoothShading::Time,
             color_map: &self.color_map,
+            mix_palettes: &self.mix,
+            column_palette: &self.column_palette,
             glitch_map: &self.glitch_map,
             char_pool: &self.char_pool,
         };
@@ -1033,25 +1724,43 @@ impl Cloud {
 mod tests {
     use std::time::{Duration, Instant};
 
-    use super::Cloud;
+    use crossterm::style::Color;
+
+    use super::{
+        blend, distance_shade, dither_levels, dither_pick, shade_lab, CharLoc, Cloud, DrawCtx,
+        REDUCED_MOTION_FADE, REDUCED_MOTION_MAX_CPS, REDUCED_MOTION_SLOWDOWN,
+    };
     use crate::frame::Frame;
+    use crate::oklab::{lightness_ramp, Oklab};
+    use crate::palette::color_to_rgb;
     use crate::runtime::{BoldMode, ColorMode, ColorScheme, ShadingMode};
 
     fn make_cloud() -> Cloud {
-        let mut cloud = Cloud::new(
-            ColorMode::Mono,
-            false,
-            ShadingMode::Random,
-            BoldMode::Off,
-            false,
-            true,
-            ColorScheme::Green,
-        );
+        let mut cloud = Cloud::builder()
+            .color_mode(ColorMode::Mono)
+            .shading_mode(ShadingMode::Random)
+            .bold_mode(BoldMode::Off)
+            .default_background(true)
+            .seed(0x1234567)
+            .build();
         cloud.init_chars(vec!['0', '1']);
         cloud.reset(20, 10);
         cloud
     }
 
+    fn run_seeded(seed: u64, frames: u32) -> Frame {
+        let mut cloud = Cloud::builder().async_mode(true).seed(seed).build();
+        cloud.set_glitchy(true);
+        cloud.init_chars(vec!['a', 'b', 'c', 'd', 'e', 'f']);
+        let start = Instant::now();
+        cloud.reset_at(20, 10, start);
+        let mut frame = Frame::new(20, 10, cloud.palette.bg);
+        for i in 1..=frames {
+            cloud.rain_at(&mut frame, start + Duration::from_millis(50) * i);
+        }
+        frame
+    }
+
     #[test]
     fn rain_produces_dirty_frame_when_time_advances() {
         let mut cloud = make_cloud();
@@ -1082,4 +1791,295 @@ mod tests {
         cloud.rain(&mut frame);
         assert!(frame.is_dirty_all() || !frame.dirty_indices().is_empty());
     }
+
+    #[test]
+    fn same_seed_gives_identical_frames() {
+        let a = run_seeded(42, 40);
+        let b = run_seeded(42, 40);
+        let c = run_seeded(43, 40);
+        assert_eq!(a.cells, b.cells);
+        assert_ne!(a.cells, c.cells);
+    }
+
+    #[test]
+    fn dithering_mixes_the_two_nearest_levels() {
+        let (a, b, c) = (
+            Color::AnsiValue(22),
+            Color::AnsiValue(28),
+            Color::AnsiValue(34),
+        );
+        let levels = dither_levels(&[a, a, b, b, c]);
+        assert_eq!(levels, vec![(0.5, a), (2.5, b), (4.0, c)]);
+
+        let count = |pos: f32, color: Color| {
+            (0..4u16)
+                .flat_map(|y| (0..4u16).map(move |x| (x, y)))
+                .filter(|&(x, y)| dither_pick(&levels, pos, x, y) == Some(color))
+                .count()
+        };
+        assert_eq!(count(0.2, a), 16);
+        assert_eq!(count(1.5, a), 8);
+        assert_eq!(count(1.5, b), 8);
+        assert_eq!(count(2.0, b), 12);
+        assert_eq!(count(4.0, c), 16);
+    }
+
+    #[test]
+    fn smooth_shading_blends_between_palette_steps() {
+        // Whole cells land where the stepped shading puts them...
+        assert_eq!(distance_shade(0.0, 10, 8.0), 8.0);
+        assert_eq!(distance_shade(10.0, 10, 8.0), 0.0);
+        // ...and positions in between move continuously.
+        let a = distance_shade(4.0, 10, 8.0);
+        let b = distance_shade(4.5, 10, 8.0);
+        let c = distance_shade(5.0, 10, 8.0);
+        assert!(a > b && b > c, "{} {} {}", a, b, c);
+
+        let lab = shade_lab(&[Color::Rgb { r: 0, g: 40, b: 0 }, Color::AnsiValue(231)]);
+        assert_eq!(blend(&lab, 0.0), Some(Color::Rgb { r: 0, g: 40, b: 0 }));
+        assert_eq!(
+            blend(&lab, 1.0),
+            Some(Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            })
+        );
+        let Some(Color::Rgb { g, .. }) = blend(&lab, 0.5) else {
+            panic!("expected an RGB color");
+        };
+        assert!(g > 40 && g < 255);
+    }
+
+    #[test]
+    fn gradient_modes_shade_by_column_row_time_and_age() {
+        let palette: Vec<Color> = (0..5).map(Color::AnsiValue).collect();
+        let start = Instant::now();
+        let ctx = |shading_mode| DrawCtx {
+            lines: 5,
+            cols: 9,
+            full_width: false,
+            shading_distance: false,
+            shading_mode,
+            shade_start_time: start,
+            bg: None,
+            color_mode: ColorMode::Color256,
+            bold_mode: BoldMode::Off,
+            glitchy: false,
+            reduced_motion: false,
+            last_glitch_time: start,
+            next_glitch_time: start,
+            palette_colors: &palette,
+            dither_levels: None,
+            shade_lab: None,
+            shade_over_time: false,
+            color_map: &[],
+            mix_palettes: &[],
+            column_palette: &[],
+            glitch_map: &[],
+            char_pool: &[],
+        };
+        let fg = |mode, line, col, at: Duration, age| {
+            ctx(mode)
+                .get_attr(line, col, 'x', CharLoc::Middle, start + at, 4, 0.0, 4, age)
+                .0
+        };
+        let zero = Duration::ZERO;
+
+        assert_eq!(
+            fg(ShadingMode::Row, 4, 0, zero, 0.0),
+            Some(Color::AnsiValue(4))
+        );
+
+        // Time shades like distance-from-head and turns the hue of every
+        // color once round the wheel every 8 seconds.
+        let ramp: Vec<Color> = lightness_ramp((200, 40, 40), 5)
+            .into_iter()
+            .map(|(r, g, b)| Color::Rgb { r, g, b })
+            .collect();
+        let time = DrawCtx {
+            color_mode: ColorMode::TrueColor,
+            palette_colors: &ramp,
+            ..ctx(ShadingMode::Time)
+        };
+        let lch = |at: Duration| {
+            let (fg, _) = time.get_attr(2, 1, 'x', CharLoc::Middle, start + at, 4, 0.0, 4, 0.0);
+            let lab = Oklab::from_rgb(color_to_rgb(fg.unwrap()).unwrap());
+            (lab.l, lab.b.atan2(lab.a).to_degrees())
+        };
+        let (l0, h0) = lch(zero);
+        let expected = Oklab::from_rgb(color_to_rgb(ramp[1]).unwrap());
+        assert!((l0 - expected.l).abs() < 0.01, "{}", l0);
+        let (l4, h4) = lch(Duration::from_secs(4));
+        assert!((l4 - l0).abs() < 0.02, "{} vs {}", l4, l0);
+        assert!(
+            ((h4 - h0).rem_euclid(360.0) - 180.0).abs() < 5.0,
+            "{} vs {}",
+            h4,
+            h0
+        );
+        let (_, h8) = lch(Duration::from_secs(8));
+        assert!((h8 - h0).abs() < 2.0, "{} vs {}", h8, h0);
+
+        // Column shades the same way and turns the hue by column instead,
+        // once round the wheel from the left edge to the right.
+        let column = DrawCtx {
+            cols: 8,
+            shading_mode: ShadingMode::Column,
+            ..time
+        };
+        let lch = |col| {
+            let (fg, _) = column.get_attr(2, col, 'x', CharLoc::Middle, start, 4, 0.0, 4, 0.0);
+            let lab = Oklab::from_rgb(color_to_rgb(fg.unwrap()).unwrap());
+            (lab.l, lab.b.atan2(lab.a).to_degrees())
+        };
+        let (c0, h0) = lch(0);
+        assert!((c0 - expected.l).abs() < 0.01, "{}", c0);
+        let (c4, h4) = lch(4);
+        assert!((c4 - c0).abs() < 0.02, "{} vs {}", c4, c0);
+        assert!(
+            ((h4 - h0).rem_euclid(360.0) - 180.0).abs() < 5.0,
+            "{} vs {}",
+            h4,
+            h0
+        );
+        assert!(column.recolors_body());
+
+        assert_eq!(
+            fg(ShadingMode::Age, 1, 1, zero, 0.0),
+            Some(Color::AnsiValue(4))
+        );
+        assert_eq!(
+            fg(ShadingMode::Age, 1, 1, zero, 1.0),
+            Some(Color::AnsiValue(0))
+        );
+        assert!(ctx(ShadingMode::Age).recolors_body());
+        assert!(!ctx(ShadingMode::Row).recolors_body());
+    }
+
+    #[test]
+    fn theme_changes_can_crossfade() {
+        use crate::palette::{build_palette, color_to_rgb};
+
+        let mut cloud = Cloud::builder().bold_mode(BoldMode::Off).seed(7).build();
+        cloud.init_chars(vec!['a', 'b']);
+        let start = Instant::now();
+        cloud.reset_at(20, 10, start);
+        let mut frame = Frame::new(20, 10, cloud.palette.bg);
+        let green = cloud.palette.colors.clone();
+        let fire = build_palette(&ColorScheme::Fire, ColorMode::TrueColor, false).colors;
+
+        cloud.fade_to_color_scheme(ColorScheme::Fire, Duration::from_secs(2), start);
+        assert_eq!(cloud.color_scheme(), &ColorScheme::Fire);
+        assert_eq!(cloud.palette.colors.len(), fire.len());
+        let rgb0 = |c: &Cloud| color_to_rgb(c.palette.colors[0]);
+        assert_eq!(rgb0(&cloud), color_to_rgb(green[0]));
+
+        cloud.rain_at(&mut frame, start + Duration::from_secs(1));
+        assert!(cloud.is_fading());
+        assert_ne!(cloud.palette.colors, fire);
+        assert_ne!(rgb0(&cloud), color_to_rgb(green[0]));
+
+        cloud.rain_at(&mut frame, start + Duration::from_secs(2));
+        assert!(!cloud.is_fading());
+        assert_eq!(cloud.palette.colors, fire);
+    }
+
+    #[test]
+    fn reduced_motion_slows_rain_and_fades_theme_changes() {
+        use crate::palette::color_to_rgb;
+
+        let mut cloud = Cloud::builder().bold_mode(BoldMode::Off).seed(7).build();
+        cloud.init_chars(vec!['a', 'b']);
+        let start = Instant::now();
+        cloud.reset_at(20, 10, start);
+        cloud.set_chars_per_sec(20.0);
+        let dps = cloud.droplets_per_sec;
+
+        cloud.set_reduced_motion(true);
+        assert_eq!(cloud.motion_chars_per_sec(), REDUCED_MOTION_MAX_CPS);
+        assert!(cloud.droplets_per_sec < dps * REDUCED_MOTION_SLOWDOWN);
+
+        // Nothing is on screen yet, so there is nothing to fade from.
+        cloud.set_color_scheme(ColorScheme::Ocean);
+        assert!(!cloud.is_fading());
+        cloud.set_color_scheme(ColorScheme::Green);
+
+        let mut frame = Frame::new(20, 10, cloud.palette.bg);
+        cloud.rain_at(&mut frame, start + Duration::from_secs(1));
+        let green = cloud.palette.colors.clone();
+        cloud.set_color_scheme(ColorScheme::Fire);
+        assert_eq!(cloud.color_scheme(), &ColorScheme::Fire);
+        assert!(cloud.is_fading());
+        assert_eq!(
+            color_to_rgb(cloud.palette.colors[0]),
+            color_to_rgb(green[0])
+        );
+        // The fade runs on the frame clock, not the wall clock.
+        cloud.rain_at(
+            &mut frame,
+            start + Duration::from_secs(1) + REDUCED_MOTION_FADE,
+        );
+        assert!(!cloud.is_fading());
+    }
+
+    #[test]
+    fn reduced_motion_fades_redraws_instead_of_clearing() {
+        let mut cloud = Cloud::builder().bold_mode(BoldMode::Off).seed(7).build();
+        cloud.init_chars(vec!['a', 'b']);
+        cloud.set_reduced_motion(true);
+        let start = Instant::now();
+        cloud.reset_at(20, 10, start);
+        let mut frame = Frame::new(20, 10, cloud.palette.bg);
+        let glyphs = |f: &Frame| (0..200).filter(|&i| f.cell_at_index(i).ch != ' ').count();
+
+        let mut t = start;
+        for _ in 0..100 {
+            t += Duration::from_millis(50);
+            cloud.rain_at(&mut frame, t);
+        }
+        let before = glyphs(&frame);
+        assert!(before > 0);
+
+        // A reset (Space) empties the rain, but the screen fades out.
+        cloud.reset_at(20, 10, t);
+        cloud.rain_at(&mut frame, t + Duration::from_millis(50));
+        assert!(glyphs(&frame) > 0);
+    }
+
+    #[test]
+    fn mixed_themes_color_each_column_from_its_own_palette() {
+        use crate::palette::build_palette;
+        use crate::runtime::parse_color_scheme;
+
+        let mix = parse_color_scheme("mix:red,blue").unwrap();
+        let mut cloud = Cloud::builder()
+            .bold_mode(BoldMode::Off)
+            .color_scheme(mix)
+            .seed(3)
+            .build();
+        cloud.init_chars(vec!['a']);
+        let start = Instant::now();
+        cloud.reset_at(40, 10, start);
+        assert_eq!(cloud.column_palette.len(), 40);
+        assert!(cloud.column_palette.contains(&0) && cloud.column_palette.contains(&1));
+
+        let mut frame = Frame::new(40, 10, cloud.palette.bg);
+        for i in 1..=60 {
+            cloud.rain_at(&mut frame, start + Duration::from_millis(50) * i);
+        }
+        let palettes = [ColorScheme::Red, ColorScheme::Blue]
+            .map(|c| build_palette(&c, ColorMode::TrueColor, false).colors);
+        let mut seen = 0;
+        for col in 0..40 {
+            let own = &palettes[cloud.column_palette[col as usize] as usize];
+            for line in 0..10 {
+                if let Some(fg) = frame.get(col, line).and_then(|c| c.fg) {
+                    assert!(own.contains(&fg), "col {} line {}: {:?}", col, line, fg);
+                    seen += 1;
+                }
+            }
+        }
+        assert!(seen > 0);
+    }
 }
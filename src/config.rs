@@ -1,9 +1,15 @@
 // Copyright (c) 2026 rezky_nightky
 
 use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use cosmostrix::script::SCRIPTS;
+
+use crate::config_file::ThemeFile;
+use crate::play::{MAX_SPEED, MIN_SPEED};
 
 pub const DEFAULT_PARAMS_USAGE: &str = "DEFAULT PARAMS USAGE:\n  cosmostrix --duration 0 --noglitch --color-bg black --color green --charset binary --fps 60 --speed 8 --density 1 --maxdpc 3 --bold 1 --shadingmode 1 --glitchpct 10 --glitchms 300,400 --lingerms 1,3000 --shortpct 50 --rippct 33.33333";
 
@@ -17,6 +23,12 @@ pub fn color_enabled_stdout() -> bool {
     std::io::stdout().is_terminal()
 }
 
+/// Whether `REDUCE_MOTION` asks for [`Args::reduced_motion`] by default;
+/// like `NO_COLOR`, any non-empty value other than `0` counts.
+pub fn reduced_motion_from_env() -> bool {
+    std::env::var_os("REDUCE_MOTION").is_some_and(|v| !v.is_empty() && v != "0")
+}
+
 fn colorize_help_detail(text: &str) -> String {
     let mut out = String::with_capacity(text.len() + 64);
     for chunk in text.split_inclusive('\n') {
@@ -90,6 +102,63 @@ pub enum ColorBg {
     Transparent,
 }
 
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoothShadingArg {
+    #[value(name = "off")]
+    Off,
+    #[value(name = "length")]
+    Length,
+    #[value(name = "time")]
+    Time,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CvdArg {
+    #[value(name = "protan", alias = "protanopia")]
+    Protan,
+    #[value(name = "deutan", alias = "deuteranopia")]
+    Deutan,
+    #[value(name = "tritan", alias = "tritanopia")]
+    Tritan,
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    #[value(name = "png")]
+    Png,
+    #[value(name = "ppm")]
+    Ppm,
+}
+
+/// `WIDTHxHEIGHT`, e.g. `80x24` or `8x16`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Size {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (a, b) = s
+            .split_once(['x', 'X'])
+            .ok_or_else(|| "expected: WIDTHxHEIGHT".to_string())?;
+        let width: u16 = a.trim().parse().map_err(|_| "invalid width".to_string())?;
+        let height: u16 = b.trim().parse().map_err(|_| "invalid height".to_string())?;
+        if width == 0 || height == 0 {
+            return Err("width and height must be > 0".to_string());
+        }
+        Ok(Self { width, height })
+    }
+}
+
+impl std::fmt::Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct U16Range {
     pub low: u16,
@@ -118,8 +187,97 @@ impl FromStr for U16Range {
     }
 }
 
+/// Parses a duration such as `30s`, `1.5m`, `500ms` or `1h`; a bare number
+/// is seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let t = s.trim();
+    let (num, unit) = t.split_at(t.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(t.len()));
+    let invalid = || format!("invalid duration {:?} (e.g. 30s, 2m, 500ms)", s);
+    let v: f64 = num.trim().parse().map_err(|_| invalid())?;
+    let scale = match unit {
+        "" | "s" => 1.0,
+        "ms" => 0.001,
+        "m" | "min" => 60.0,
+        "h" => 3600.0,
+        _ => return Err(invalid()),
+    };
+    Duration::try_from_secs_f64(v * scale).map_err(|_| invalid())
+}
+
+/// `INTERVAL[,THEME,...]` for `--cycle-colors`, e.g. `30s,fire,ocean`.
+#[derive(Clone, Debug)]
+pub struct CycleColorsArg {
+    pub interval: Duration,
+    pub themes: Vec<String>,
+}
+
+impl FromStr for CycleColorsArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(',');
+        let interval = parse_duration(parts.next().unwrap_or_default())?;
+        if interval < Duration::from_secs(1) || interval > Duration::from_secs(86400) {
+            return Err("interval must be between 1s and 24h".to_string());
+        }
+        let themes: Vec<String> = parts.map(|p| p.trim().to_string()).collect();
+        if themes.iter().any(|t| t.is_empty()) {
+            return Err("empty theme name in list".to_string());
+        }
+        Ok(Self { interval, themes })
+    }
+}
+
+/// Parses a `play --speed` multiplier, rejecting values outside
+/// [`MIN_SPEED`]..=[`MAX_SPEED`].
+pub fn parse_speed(s: &str) -> Result<f64, String> {
+    let v: f64 = s
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid speed {:?} (e.g. 0.5, 2)", s))?;
+    if !(MIN_SPEED..=MAX_SPEED).contains(&v) {
+        return Err(format!("{} (min {} max {})", v, MIN_SPEED, MAX_SPEED));
+    }
+    Ok(v)
+}
+
+// Subcommands; without one, cosmostrix shows the rain.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    #[command(
+        about = "Replay an asciicast v2 recording (see --record)",
+        after_help = "KEYS:\n  q / Esc      quit\n  Space / p    pause/resume\n  Up / Down    double/halve speed\n  Left / Right seek 5 seconds\n  Home         restart"
+    )]
+    Play(PlayArgs),
+}
+
+/// `cosmostrix play FILE`: replay a recording made with `--record`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct PlayArgs {
+    #[arg(value_name = "FILE", help = "Recording to play")]
+    pub file: PathBuf,
+
+    #[arg(
+        long = "speed",
+        value_name = "MULT",
+        default_value_t = 1.0,
+        value_parser = parse_speed,
+        help = "Playback speed multiplier (min 0.0625 max 16)"
+    )]
+    pub speed: f64,
+
+    #[arg(long = "loop", help = "Start over when the recording ends")]
+    pub looping: bool,
+}
+
 #[derive(Parser, Debug, Clone)]
-#[command(name = "cosmostrix", version, disable_version_flag = true)]
+#[command(
+    name = "cosmostrix",
+    version,
+    disable_version_flag = true,
+    disable_help_subcommand = true,
+    args_conflicts_with_subcommands = true
+)]
 pub struct Args {
     #[arg(
         short = 'a',
@@ -133,6 +291,22 @@ pub struct Args {
     )]
     pub async_mode: bool,
 
+    #[arg(
+        long = "config",
+        value_name = "PATH",
+        help_heading = "GENERAL",
+        help = "Config file (default: $XDG_CONFIG_HOME/cosmostrix/config.toml)"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long = "profile",
+        value_name = "NAME",
+        help_heading = "GENERAL",
+        help = "Apply [profile.NAME] from the config file (see --list-profiles)"
+    )]
+    pub profile: Option<String>,
+
     #[arg(
         short = 'b',
         long = "bold",
@@ -151,6 +325,14 @@ pub struct Args {
     )]
     pub color: String,
 
+    #[arg(
+        long = "theme-file",
+        value_name = "PATH",
+        help_heading = "APPEARANCE",
+        help = "Custom themes (default: $XDG_CONFIG_HOME/cosmostrix/themes.toml)"
+    )]
+    pub theme_file: Option<PathBuf>,
+
     #[arg(
         long = "color-bg",
         default_value_t = ColorBg::Black,
@@ -160,6 +342,85 @@ pub struct Args {
     )]
     pub color_bg: ColorBg,
 
+    #[arg(
+        long = "cycle-colors",
+        value_name = "INTERVAL[,THEME...]",
+        help_heading = "APPEARANCE",
+        help = "Change theme every INTERVAL (e.g. 30s), through the listed themes or all of them"
+    )]
+    pub cycle_colors: Option<CycleColorsArg>,
+
+    #[arg(
+        long = "crossfade",
+        value_name = "DURATION",
+        default_value = "2s",
+        value_parser = parse_duration,
+        help_heading = "APPEARANCE",
+        help = "Fade time between --cycle-colors themes (min 0 max 60s, 0 = instant)"
+    )]
+    pub crossfade: Duration,
+
+    #[arg(
+        long = "dither",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help_heading = "APPEARANCE",
+        help = "Ordered dithering of gradients in 16/256-color modes (default: off)"
+    )]
+    pub dither: bool,
+
+    #[arg(
+        long = "smooth-shading",
+        default_value_t = SmoothShadingArg::Off,
+        value_enum,
+        help_heading = "APPEARANCE",
+        help = "Continuous truecolor shading with -M 1 to 5 (off, length, time)"
+    )]
+    pub smooth_shading: SmoothShadingArg,
+
+    #[arg(
+        long = "brightness",
+        default_value_t = 1.0,
+        help_heading = "APPEARANCE",
+        help = "Theme brightness factor (min 0.1 max 3, default 1)"
+    )]
+    pub brightness: f32,
+
+    #[arg(
+        long = "contrast",
+        default_value_t = 1.0,
+        help_heading = "APPEARANCE",
+        help = "Theme contrast factor (min 0.1 max 3, default 1)"
+    )]
+    pub contrast: f32,
+
+    #[arg(
+        long = "saturation",
+        default_value_t = 1.0,
+        help_heading = "APPEARANCE",
+        help = "Theme saturation factor (min 0 max 3, default 1; 0 = gray)"
+    )]
+    pub saturation: f32,
+
+    #[arg(
+        long = "hue-shift",
+        default_value_t = 0.0,
+        allow_negative_numbers = true,
+        help_heading = "APPEARANCE",
+        help = "Rotate theme hues by DEGREES (min -180 max 180)"
+    )]
+    pub hue_shift: f32,
+
+    #[arg(
+        long = "simulate-cvd",
+        value_enum,
+        help_heading = "APPEARANCE",
+        help = "Preview the theme as seen with a color-vision deficiency (protan, deutan, tritan)"
+    )]
+    pub simulate_cvd: Option<CvdArg>,
+
     #[arg(
         short = 'd',
         long = "density",
@@ -172,6 +433,10 @@ pub struct Args {
     #[arg(
         short = 'F',
         long = "fullwidth",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
         help_heading = "GENERAL",
         help = "Use full terminal width"
     )]
@@ -186,6 +451,17 @@ pub struct Args {
     )]
     pub fps: f64,
 
+    #[arg(
+        long = "reduced-motion",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
+        help_heading = "GENERAL",
+        help = "Photosensitivity-safe rendering: slower rain, no flashes, fading theme changes"
+    )]
+    pub reduced_motion: bool,
+
     #[arg(
         long = "duration",
         help_heading = "GENERAL",
@@ -195,6 +471,10 @@ pub struct Args {
 
     #[arg(
         long = "perf-stats",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
         help_heading = "PERFORMANCE",
         help = "Print performance statistics summary on exit"
     )]
@@ -207,6 +487,74 @@ pub struct Args {
     )]
     pub bench_frames: Option<u64>,
 
+    #[arg(
+        long = "export-frames",
+        value_name = "DIR",
+        help_heading = "EXPORT",
+        help = "Render headlessly to an image sequence in DIR and exit"
+    )]
+    pub export_frames: Option<PathBuf>,
+
+    #[arg(
+        long = "export-gif",
+        value_name = "FILE",
+        help_heading = "EXPORT",
+        help = "Render headlessly to an animated GIF and exit"
+    )]
+    pub export_gif: Option<PathBuf>,
+
+    #[arg(
+        long = "export-format",
+        default_value_t = ExportFormat::Png,
+        value_enum,
+        help_heading = "EXPORT",
+        help = "Image format for --export-frames (png, ppm)"
+    )]
+    pub export_format: ExportFormat,
+
+    #[arg(
+        long = "frames",
+        value_name = "N",
+        default_value_t = 120,
+        help_heading = "EXPORT",
+        help = "Number of frames for --export-frames/--export-gif (min 1 max 100000)"
+    )]
+    pub frames: u32,
+
+    #[arg(
+        long = "size",
+        value_name = "COLSxLINES",
+        default_value = "80x24",
+        help_heading = "EXPORT",
+        help = "Virtual terminal size for --export-frames/--export-gif"
+    )]
+    pub size: Size,
+
+    #[arg(
+        long = "cell-size",
+        value_name = "WxH",
+        default_value = "8x16",
+        help_heading = "EXPORT",
+        help = "Pixels per character cell for export (min 1 max 64)"
+    )]
+    pub cell_size: Size,
+
+    #[arg(
+        long = "seed",
+        value_name = "N",
+        help_heading = "GENERAL",
+        help = "Random seed for a reproducible animation (default: random, shown by --perf-stats)"
+    )]
+    pub seed: Option<u64>,
+
+    #[arg(
+        long = "record",
+        value_name = "FILE",
+        help_heading = "GENERAL",
+        help = "Record the session to an asciicast v2 file (play with asciinema)"
+    )]
+    pub record: Option<PathBuf>,
+
     #[arg(
         short = 'g',
         long = "glitchms",
@@ -239,7 +587,7 @@ pub struct Args {
         long = "shadingmode",
         default_value_t = 1,
         help_heading = "APPEARANCE",
-        help = "Shading mode (min 0 max 1): 0=random, 1=distance-from-head (default)"
+        help = "Shading mode (min 0 max 5): 0=random, 1=distance-from-head (default), 2=column, 3=row, 4=time, 5=age"
     )]
     pub shading_mode: u8,
 
@@ -253,6 +601,10 @@ pub struct Args {
 
     #[arg(
         long = "message-no-border",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
         help_heading = "GENERAL",
         help = "Draw message box without border (use with --message; shorthand: -mB)"
     )]
@@ -298,6 +650,10 @@ pub struct Args {
     #[arg(
         short = 's',
         long = "screensaver",
+        default_value_t = false,
+        action = clap::ArgAction::Set,
+        num_args = 0..=1,
+        default_missing_value = "true",
         help_heading = "GENERAL",
         help = "Screensaver mode (exit on keypress)"
     )]
@@ -315,17 +671,31 @@ pub struct Args {
         long = "charset",
         default_value = "binary",
         help_heading = "CHARSET",
-        help = "Charset preset (see --list-charsets)"
+        help = "Charset preset or expression, e.g. katakana:3,digits:1 (see --list-charsets)"
     )]
     pub charset: String,
 
     #[arg(
         long = "chars",
         help_heading = "CHARSET",
-        help = "Custom characters override"
+        help = "Custom code points: U+30A0..U+30FF ranges, U+2588, or hex pairs 30,39"
     )]
     pub chars: Option<String>,
 
+    #[arg(
+        long = "chars-literal",
+        help_heading = "CHARSET",
+        help = "Custom characters, written out (e.g. \"01アイウ\")"
+    )]
+    pub chars_literal: Option<String>,
+
+    #[arg(
+        long = "chars-file",
+        help_heading = "CHARSET",
+        help = "Read custom characters from a UTF-8 text file"
+    )]
+    pub chars_file: Option<PathBuf>,
+
     #[arg(
         long = "colormode",
         help_heading = "APPEARANCE",
@@ -361,6 +731,13 @@ pub struct Args {
     )]
     pub list_charsets: bool,
 
+    #[arg(
+        long = "list-scripts",
+        help_heading = "HELP",
+        help = "List Unicode scripts for --charset script:NAME and exit"
+    )]
+    pub list_scripts: bool,
+
     #[arg(
         long = "list-colors",
         help_heading = "HELP",
@@ -368,6 +745,13 @@ pub struct Args {
     )]
     pub list_colors: bool,
 
+    #[arg(
+        long = "list-profiles",
+        help_heading = "HELP",
+        help = "List profiles defined in the config file and exit"
+    )]
+    pub list_profiles: bool,
+
     #[arg(
         long = "info",
         short = 'i',
@@ -383,6 +767,9 @@ pub struct Args {
         help = "Print version and exit"
     )]
     pub version: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
 }
 
 pub fn print_list_charsets() {
@@ -421,7 +808,31 @@ pub fn print_list_charsets() {
     println!("runic        Runic");
 }
 
-pub fn print_list_colors() {
+pub fn print_list_scripts() {
+    if color_enabled_stdout() {
+        println!("\x1b[1;36mAVAILABLE SCRIPTS:\x1b[0m");
+        println!("\x1b[2mNOTE: Use as --charset script:NAME (any case).\x1b[0m");
+    } else {
+        println!("AVAILABLE SCRIPTS:");
+        println!("NOTE: Use as --charset script:NAME (any case).");
+    }
+    println!();
+    println!("{:<20} {:>6}  SAMPLE", "NAME", "CHARS");
+    for s in SCRIPTS {
+        let chars = s.chars();
+        let step = (chars.len() / 6).max(1);
+        let sample: String = chars.iter().step_by(step).take(6).collect();
+        println!(
+            "{:<20} {:>6}  {}{}",
+            s.name,
+            chars.len(),
+            sample,
+            if s.wide { "  (wide, turns on -F)" } else { "" }
+        );
+    }
+}
+
+pub fn print_list_colors(theme_file: Option<&ThemeFile>) {
     if color_enabled_stdout() {
         println!("\x1b[1;36mAVAILABLE COLOR THEMES:\x1b[0m");
         println!("\x1b[2mNOTE: Use only the VALUE (left side) with --color.\x1b[0m");
@@ -474,11 +885,59 @@ pub fn print_list_colors() {
     println!("meteor       Meteor theme");
     println!("eclipse      Eclipse theme");
     println!("deepspace    Deep space theme");
+    println!("viridis      Purple to yellow (colorblind-safe)");
+    println!("cividis      Navy to yellow (colorblind-safe)");
+    println!("magma        Black to pale yellow (colorblind-safe)");
+    println!("#RRGGBB      Shades of one color (also rgb(r,g,b))");
+    println!("A+B          Tails from A, heads from B (e.g. fire+ocean, up to 16 themes)");
+    println!("mix:A,B,...  Each column takes one of the themes (e.g. mix:neon,green,gold)");
+
+    let Some(file) = theme_file.filter(|f| !f.themes.is_empty()) else {
+        return;
+    };
+    println!();
+    println!("CUSTOM THEMES ({}):", file.path.display());
+    for t in &file.themes {
+        println!(
+            "{:<12} {}",
+            t.name,
+            t.description.as_deref().unwrap_or("Custom theme")
+        );
+    }
+}
+
+pub fn print_list_profiles(path: Option<&Path>, profiles: &[(&str, Option<&str>)]) {
+    if color_enabled_stdout() {
+        println!("\x1b[1;36mAVAILABLE PROFILES:\x1b[0m");
+        println!("\x1b[2mNOTE: Use only the VALUE (left side) with --profile.\x1b[0m");
+    } else {
+        println!("AVAILABLE PROFILES:");
+        println!("NOTE: Use only the VALUE (left side) with --profile.");
+    }
+    match path {
+        Some(p) => println!("CONFIG: {}", p.display()),
+        None => println!("CONFIG: (no config file found)"),
+    }
+    println!();
+    if profiles.is_empty() {
+        println!("(none; add a [profile.NAME] table to the config file)");
+        return;
+    }
+    let width = profiles
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(12);
+    println!("{:<width$} DESCRIPTION", "VALUE", width = width);
+    for (name, desc) in profiles {
+        println!("{:<width$} {}", name, desc.unwrap_or(""), width = width);
+    }
 }
 
 pub fn print_help_detail() {
     let block = format!(
-        "{}\n\nUSAGE:\n  cosmostrix [OPTIONS]\n\nGENERAL:\n  -a, --async\n      Async rendering (default: off).\n      To enable: --async or --async=true\n      Example: cosmostrix --async\n\n  -s, --screensaver\n      Screensaver mode (exit on keypress).\n      Example: cosmostrix -s\n\n  -F, --fullwidth\n      Use full terminal width.\n      Example: cosmostrix -F\n\n  --duration <seconds>\n      Stop after N seconds (min 0.1 max 86400).\n      Example: cosmostrix --duration 10\n\n  --check-bitcolor\n      Print detected terminal color capability and exit.\n      Example: cosmostrix --check-bitcolor\n\n  -m, --message <text>\n      Overlay message.\n      Example: cosmostrix -m \"hello\"\n\nAPPEARANCE:\n  -c, --color <name>\n      Set theme (see --list-colors).\n      Example: cosmostrix --color rainbow\n\n  --colormode <0|8|24>\n      Force color mode; otherwise auto-detected from COLORTERM/TERM.\n      Example: cosmostrix --colormode 24\n\n  -b, --bold <0|1|2>\n      Bold style (0 off, 1 random, 2 all).\n      Example: cosmostrix --bold 2\n\n  -M, --shadingmode <0|1>\n      Shading (0 random, 1 distance-from-head).\n      Example: cosmostrix -M 1\n\n  --color-bg <black|default-background|transparent>\n      Background mode.\n      Example: cosmostrix --color-bg transparent\n\nPERFORMANCE:\n  -f, --fps <number>\n      Target FPS (min 1 max 240).\n      Example: cosmostrix --fps 30\n\n  -S, --speed <number>\n      Characters per second (rain speed) (min 0.001 max 1000).\n      Example: cosmostrix --speed 12\n\n  -d, --density <number>\n      Droplet density (min 0.01 max 5.0).\n      Example: cosmostrix --density 1.25\n\n  --maxdpc <number>\n      Max droplets per column (min 1 max 3).\n      Example: cosmostrix --maxdpc 2\n\n  --perf-stats\n      Print performance statistics summary on exit.\n      Example: cosmostrix --duration 10 --perf-stats\n\nCHARSET:\n  --charset <name>\n      Charset preset (see --list-charsets).\n      Example: cosmostrix --charset binary\n\n  --chars <string>\n      Custom character override (advanced).\n      Example: cosmostrix --chars \"01\"\n\nGLITCH (ADVANCED):\n  --noglitch\n      Disable glitch effects (default: on).\n      To enable glitch: --noglitch=false\n      Example: cosmostrix --noglitch=false\n\n  -G, --glitchpct <number>\n      Glitch chance in percent (min 0 max 100).\n      Example: cosmostrix --glitchpct 5\n\n  -g, --glitchms <low,high>\n      Glitch duration range in ms (min 1 max 5000).\n      Example: cosmostrix --glitchms 200,500\n\n  -l, --lingerms <low,high>\n      Linger duration range in ms (min 1 max 60000).\n      Example: cosmostrix --lingerms 1,3000\n\n  --shortpct <number>\n      Short droplet chance in percent (min 0 max 100).\n      Example: cosmostrix --shortpct 40\n\n  -r, --rippct <number>\n      Die-early chance in percent (min 0 max 100).\n      Example: cosmostrix --rippct 20\n\nHELP:\n  --check-bitcolor\n      Print detected terminal color capability and exit.\n\n  --help\n      Show short help.\n\n  --help-detail\n      Show this detailed help.\n\n  --list-charsets\n      List available charset presets and exit.\n\n  --list-colors\n      List available color themes and exit.\n\n  -v, --version\n      Print version and exit.\n\n  -i, --info\n      Print version info and exit.\n",
+        "{}\n\nUSAGE:\n  cosmostrix [OPTIONS]\n  cosmostrix play [--speed <mult>] [--loop] <file>\n\nGENERAL:\n  -a, --async\n      Async rendering (default: off).\n      To enable: --async or --async=true\n      Example: cosmostrix --async\n\n  -s, --screensaver\n      Screensaver mode (exit on keypress).\n      Example: cosmostrix -s\n\n  -F, --fullwidth\n      Use full terminal width.\n      On automatically when the charset has two-cell glyphs (Han, Hangul, Hiragana, ...).\n      Example: cosmostrix -F\n\n  --reduced-motion\n      Photosensitivity-safe rendering: half speed (at most 3 chars/s) and half the droplets,\n      no glitch flashes, and theme changes fade over at least 1.5s instead of switching.\n      Outside mono mode each cell, and the screen as a whole, brightens or darkens gradually,\n      so glyphs fade in and out and redraws (Tab, Space, time shading) become fades.\n      On by default when REDUCE_MOTION is set; --reduced-motion=false turns it off.\n      Example: cosmostrix --reduced-motion\n\n  --duration <seconds>\n      Stop after N seconds (min 0.1 max 86400).\n      Example: cosmostrix --duration 10\n\n  --check-bitcolor\n      Print detected terminal color capability and exit.\n      Example: cosmostrix --check-bitcolor\n\n  -m, --message <text>\n      Overlay message.\n      Example: cosmostrix -m \"hello\"\n\n  --seed <number>\n      Seed the animation RNG; the same seed, size and options replay the same animation.\n      Without it a random seed is used (printed by --perf-stats and --bench-frames).\n      Example: cosmostrix --seed 1234\n\n  --record <file>\n      Record everything drawn (and terminal resizes) to an asciicast v2 file.\n      Combine with --seed and --duration for reproducible demo recordings.\n      Example: cosmostrix --seed 7 --duration 20 --record demo.cast\n\n  play <file>\n      Replay a recording with its original timing (crops or pads to the terminal).\n      --speed <mult> sets the speed (min 0.0625 max 16), --loop repeats forever.\n      Keys: q quit, Space pause, Up/Down speed, Left/Right seek 5s, Home restart.\n      Example: cosmostrix play --loop demo.cast\n\n  --config <path>\n      Load options from a TOML config file (default: $XDG_CONFIG_HOME/cosmostrix/config.toml).\n      Keys are long option names; precedence is CLI > profile > environment > file > default.\n      Example: cosmostrix --config ~/cosmostrix.toml\n\n  --profile <name>\n      Apply a [profile.NAME] table from the config file (overridden by explicit flags).\n      Example: cosmostrix --profile office\n\nAPPEARANCE:\n  -c, --color <name>\n      Set theme (see --list-colors).\n      Example: cosmostrix --color rainbow\n      A single color also works: #RRGGBB or rgb(r,g,b) gives a dark-to-white ramp of that hue.\n      Example: cosmostrix --color '#0077cc'\n      Combine themes: A+B splits one gradient (tails from A, heads from B); mix:A,B,... gives each column one of the themes.\n      Example: cosmostrix --color fire+ocean\n      Example: cosmostrix --color mix:neon,green,gold\n\n  --theme-file <path>\n      Load custom [theme.NAME] gradient themes (default: $XDG_CONFIG_HOME/cosmostrix/themes.toml).\n      Keys: stops = [\"#RRGGBB\" or [r, g, b], ...] (tail to head), steps (min 1 max 64, default 9), head, description.\n      Or import = \"<scheme file>\" (base16/base24 YAML, .itermcolors, Alacritty TOML, Kitty .conf) with accent = <ansi color name>.\n      Example: cosmostrix --theme-file ~/themes.toml --color sakura\n\n  --colormode <0|8|24>\n      Force color mode; otherwise auto-detected from COLORTERM/TERM.\n      Example: cosmostrix --colormode 24\n\n  -b, --bold <0|1|2>\n      Bold style (0 off, 1 random, 2 all).\n      Example: cosmostrix --bold 2\n\n  -M, --shadingmode <0-5>\n      How droplet bodies are colored (Tab cycles through the modes at runtime):\n      0 random, 1 distance-from-head (bright head fading to the tail),\n      2 column (like 1, with the hue turning once round the color wheel from left to right), 3 row (tail color at the top, head color at the bottom),\n      4 time (like 1, with the hue of the whole rain turning round the color wheel every 8 seconds), 5 age (droplets start bright and dim as they fall).\n      Example: cosmostrix -M 2 --color rainbow\n\n  --color-bg <black|default-background|transparent>\n      Background mode.\n      Example: cosmostrix --color-bg transparent\n\n  --cycle-colors <interval>[,theme,...]\n      Switch theme every interval (30s, 2m, 1h, 500ms; a bare number is seconds), in the listed order\n      or through all themes (see --list-colors). With a list, the first theme is used unless -c is given.\n      Picking a theme with c / C starts the interval over.\n      Example: cosmostrix --cycle-colors 30s,fire,ocean,aurora\n\n  --crossfade <duration>\n      How long each --cycle-colors change blends from the old theme to the new one (default: 2s, 0 = instant).\n      Example: cosmostrix --cycle-colors 1m --crossfade 5s\n\n  --dither\n      Ordered (Bayer) dithering between neighboring theme colors with gradient shading (-M 1 to 5).\n      Smooths banded gradients in 16/256-color modes; no effect in truecolor.\n      Example: cosmostrix --colormode 8 -M 1 --dither\n\n  --smooth-shading <off|length|time>\n      Truecolor only, with gradient shading (-M 1 to 5): blend colors continuously along each droplet\n      instead of stepping through theme colors (length), and also glide the gradient with the\n      falling head between whole-cell steps (time, -M 1 and 4). Lower color modes keep stepped shading.\n      Example: cosmostrix --colormode 24 -M 1 --smooth-shading time\n\n  --brightness <factor>, --contrast <factor>, --saturation <factor>, --hue-shift <degrees>\n      Adjust any theme (in perceptual OKLab): brightness scales lightness (min 0.1 max 3), contrast spreads it\n      around mid-gray (min 0.1 max 3), saturation scales colorfulness (min 0 max 3, 0 = gray) and hue-shift\n      rotates hues (min -180 max 180). Defaults: 1, 1, 1, 0. Adjustable at runtime (see RUNTIME KEYS).\n      Example: cosmostrix --color deepspace --brightness 1.4\n\n  --simulate-cvd <protan|deutan|tritan>\n      Show every color as seen with protanopia, deuteranopia or tritanopia, to check that a theme's steps stay\n      distinguishable. viridis, cividis and magma are designed to pass for all three.\n      Example: cosmostrix --color mars --simulate-cvd deutan\n\nPERFORMANCE:\n  -f, --fps <number>\n      Target FPS (min 1 max 240).\n      Example: cosmostrix --fps 30\n\n  -S, --speed <number>\n      Characters per second (rain speed) (min 0.001 max 1000).\n      Example: cosmostrix --speed 12\n\n  -d, --density <number>\n      Droplet density (min 0.01 max 5.0).\n      Example: cosmostrix --density 1.25\n\n  --maxdpc <number>\n      Max droplets per column (min 1 max 3).\n      Example: cosmostrix --maxdpc 2\n\n  --perf-stats\n      Print performance statistics summary on exit.\n      Example: cosmostrix --duration 10 --perf-stats\n\nEXPORT:\n  --export-frames <dir>\n      Render headlessly (no terminal needed) to DIR/frame-00001.png, ... and exit.\n      Frames are stepped at --fps like --bench-frames; combine with --seed to reproduce.\n      Example: cosmostrix --seed 7 --export-frames out --frames 300\n\n  --export-gif <file>\n      Render headlessly to a looping animated GIF and exit (can be combined with --export-frames).\n      Colors come from the theme palette; only changed regions are stored, so files stay small.\n      Example: cosmostrix --seed 7 --export-gif rain.gif --frames 300 --size 80x25\n\n  --export-format <png|ppm>\n      Image format (default: png).\n      Example: cosmostrix --export-frames out --export-format ppm\n\n  --frames <number>\n      Number of frames to export (default: 120, min 1 max 100000).\n      Example: cosmostrix --export-frames out --frames 600\n\n  --size <colsxlines>\n      Virtual terminal size (default: 80x24, min 1 max 1000 each).\n      Frames for --export-frames may be at most 16384 pixels on a side (with --cell-size).\n      Example: cosmostrix --export-frames out --size 160x45\n\n  --cell-size <wxh>\n      Pixels per character cell (default: 8x16, min 1 max 64).\n      Example: cosmostrix --export-frames out --cell-size 12x24\n\nCHARSET:\n  --charset <name>\n      Charset preset (see --list-charsets), or an expression of presets:\n      + adds and - removes characters (matrix+greek-punc), and comma-separated\n      terms with :WEIGHT set each term's share of the rain (katakana:3,digits:1).\n      Without weights every character is equally likely. script:NAME selects the\n      letters and digits of a Unicode script (see --list-scripts).\n      Example: cosmostrix --charset script:Hangul\n      Example: cosmostrix --charset binary\n      Example: cosmostrix --charset \"katakana:3,digits:1,symbols:0.2\"\n\n  --chars <list>\n      Custom code points: ranges (U+30A0..U+30FF), single code points (U+2588),\n      or plain hex pairs where each pair is an inclusive range (30,39,41,5A).\n      Example: cosmostrix --chars U+30A0..U+30FF,U+0030..U+0039\n\n  --chars-literal <text>\n      Custom characters written out as they should appear.\n      Example: cosmostrix --chars-literal \"01アイウ\"\n\n  --chars-file <path>\n      Read custom characters from a UTF-8 text file (line breaks are ignored).\n      Example: cosmostrix --chars-file glyphs.txt\n\n  Custom characters are added to the --charset preset; duplicates are dropped and control\n  characters are rejected.\n\nGLITCH (ADVANCED):\n  --noglitch\n      Disable glitch effects (default: on).\n      To enable glitch: --noglitch=false\n      Example: cosmostrix --noglitch=false\n\n  -G, --glitchpct <number>\n      Glitch chance in percent (min 0 max 100).\n      Example: cosmostrix --glitchpct 5\n\n  -g, --glitchms <low,high>\n      Glitch duration range in ms (min 1 max 5000).\n      Example: cosmostrix --glitchms 200,500\n\n  -l, --lingerms <low,high>\n      Linger duration range in ms (min 1 max 60000).\n      Example: cosmostrix --lingerms 1,3000\n\n  --shortpct <number>\n      Short droplet chance in percent (min 0 max 100).\n      Example: cosmostrix --shortpct 40\n\n  -r, --rippct <number>\n      Die-early chance in percent (min 0 max 100).\n      Example: cosmostrix --rippct 20\n\nHELP:\n  --check-bitcolor\n      Print detected terminal color capability and exit.\n\n  --help\n      Show short help.\n\n  --help-detail\n      Show this detailed help.\n\n  --list-charsets\n      List available charset presets and exit.\n\n  --list-scripts\n      List Unicode scripts for --charset script:NAME and exit.\n\n  --list-colors\n      List available color themes and exit.\n\n  --list-profiles\n      List profiles defined in the config file and exit.\n\n  -v, --version\n      Print version and exit.\n\n  -i, --info\n      Print version info and exit.\n",
         DEFAULT_PARAMS_USAGE
     )
     .replace(
@@ -507,21 +966,21 @@ pub fn print_help_detail() {
         print!("{}", bench);
     }
 
-    let runtime_keys = "\nRUNTIME KEYS:\n  q / Esc\n      Quit\n  p\n      Pause/resume\n  Ctrl+Z\n      Suspend (resume with: fg)\n  Space\n      Reset/reseed animation\n  Up / Down\n      Increase/decrease speed\n  [ / -\n      Decrease density\n  ] / +\n      Increase density\n  c / C\n      Cycle color theme (next/previous)\n  s / S\n      Cycle charset preset (next/previous)\n  a\n      Toggle async rendering\n  g\n      Toggle glitch effects on/off\n  Left / Right\n      Change glitch percent (when glitch is on)\n  Tab\n      Toggle shading mode\n";
+    let runtime_keys = "\nRUNTIME KEYS:\n  q / Esc\n      Quit\n  p\n      Pause/resume\n  Ctrl+Z\n      Suspend (resume with: fg)\n  Space\n      Reset/reseed animation\n  Up / Down\n      Increase/decrease speed\n  [ / -\n      Decrease density\n  ] / +\n      Increase density\n  c / C\n      Cycle color theme (next/previous)\n  s / S\n      Cycle charset preset (next/previous); your --charset and --chars stay in the cycle\n  a\n      Toggle async rendering\n  g\n      Toggle glitch effects on/off\n  Left / Right\n      Change glitch percent (when glitch is on)\n  Tab\n      Cycle shading mode\n  b / B\n      Increase/decrease brightness\n  k / K\n      Increase/decrease contrast\n  v / V\n      Increase/decrease saturation\n  h / H\n      Shift hue forward/back by 15 degrees\n  r\n      Reset brightness, contrast, saturation and hue\n";
     if color_enabled_stdout() {
         print!("{}", colorize_help_detail(runtime_keys));
     } else {
         print!("{}", runtime_keys);
     }
 
-    let env = "\nENVIRONMENT:\n  COSMOSTRIX_NO_FORK_GUARD\n      Linux only. Set to 1/true/on/yes to disable the fork-based SIGKILL (-9) terminal guard.\n      Values 0/false/off/no/empty keep the guard enabled.\n";
+    let env = "\nENVIRONMENT:\n  COSMOSTRIX_NO_FORK_GUARD\n      Linux only. Set to 1/true/on/yes to disable the fork-based SIGKILL (-9) terminal guard.\n      Values 0/false/off/no/empty keep the guard enabled.\n\n  REDUCE_MOTION\n      Set to any value other than 0/empty to turn on --reduced-motion by default.\n\n  COSMOSTRIX_<OPTION>\n      Any option from the config file, e.g. COSMOSTRIX_FPS=30 or COSMOSTRIX_COLOR_BG=transparent.\n      Overrides the config file; overridden by command-line flags.\n";
     if color_enabled_stdout() {
         print!("{}", colorize_help_detail(env));
     } else {
         print!("{}", env);
     }
 
-    let tail = "\nVALUE LISTS:\n  cosmostrix --list-charsets\n  cosmostrix --list-colors\n\nMESSAGE BOX:\n  --message-no-border, -mB\n      Draw filled box without border characters\n\nLIMITS / VALID RANGES:\n";
+    let tail = "\nVALUE LISTS:\n  cosmostrix --list-charsets\n  cosmostrix --list-scripts\n  cosmostrix --list-colors\n  cosmostrix --list-profiles\n\nMESSAGE BOX:\n  --message-no-border, -mB\n      Draw filled box without border characters\n\nLIMITS / VALID RANGES:\n";
     if color_enabled_stdout() {
         print!("{}", colorize_help_detail(tail));
     } else {
@@ -530,6 +989,9 @@ pub fn print_help_detail() {
     println!("  --duration <seconds>     min 0.1 max 86400 (<=0 disables)");
     println!("  --perf-stats             print performance summary on exit");
     println!("  --bench-frames <frames>  min 1");
+    println!("  --seed <number>          0..18446744073709551615");
+    println!("  --frames <number>        min 1 max 100000");
+    println!("  --cell-size <wxh>        min 1x1 max 64x64");
     println!("  --fps <number>           min 1 max 240");
     println!("  --speed <number>         min 0.001 max 1000");
     println!("  --density <number>       min 0.01 max 5.0");
@@ -540,10 +1002,16 @@ pub fn print_help_detail() {
     println!("  --glitchms <low,high>    min 1 max 5000 (each)");
     println!("  --lingerms <low,high>    min 1 max 60000 (each)");
     println!("  --bold <0|1|2>           min 0 max 2");
-    println!("  --shadingmode <0|1>      min 0 max 1");
+    println!("  --shadingmode <0-5>      min 0 max 5");
+    println!("  --cycle-colors <interval> min 1s max 24h");
+    println!("  --crossfade <duration>   min 0 max 60s");
+    println!("  --brightness <factor>    min 0.1 max 3");
+    println!("  --contrast <factor>      min 0.1 max 3");
+    println!("  --saturation <factor>    min 0 max 3");
+    println!("  --hue-shift <degrees>    min -180 max 180");
     println!("  --colormode <0|16|8|24>  allowed values only (8==256, 24==32)");
     println!();
     print_list_charsets();
     println!();
-    print_list_colors();
+    print_list_colors(None);
 }
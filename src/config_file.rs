@@ -0,0 +1,498 @@
+// Copyright (c) 2026 rezky_nightky
+
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Command};
+use cosmostrix::theme::parse_theme_file;
+use cosmostrix::CustomTheme;
+
+pub const ENV_PREFIX: &str = "COSMOSTRIX_";
+
+fn config_dir() -> Option<PathBuf> {
+    #[cfg(windows)]
+    {
+        if let Some(appdata) = env::var_os("APPDATA").filter(|v| !v.is_empty()) {
+            return Some(PathBuf::from(appdata).join("cosmostrix"));
+        }
+    }
+
+    let base = match env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(v) => PathBuf::from(v),
+        None => PathBuf::from(env::var_os("HOME").filter(|v| !v.is_empty())?).join(".config"),
+    };
+    Some(base.join("cosmostrix"))
+}
+
+pub fn default_config_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("config.toml"))
+}
+
+pub fn default_theme_path() -> Option<PathBuf> {
+    Some(config_dir()?.join("themes.toml"))
+}
+
+/// Custom themes and the file they were read from.
+pub struct ThemeFile {
+    pub path: PathBuf,
+    pub themes: Vec<Arc<CustomTheme>>,
+}
+
+/// Loads custom themes from `explicit` (missing file is an error) or the
+/// default location (missing file is ignored).
+pub fn load_themes(explicit: Option<&Path>) -> Result<Option<ThemeFile>, String> {
+    let (path, required) = match explicit {
+        Some(p) => (p.to_path_buf(), true),
+        None => match default_theme_path() {
+            Some(p) => (p, false),
+            None => return Ok(None),
+        },
+    };
+
+    let text = match std::fs::read_to_string(&path) {
+        Ok(t) => t,
+        Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+    };
+
+    let themes = parse_theme_file(&text, path.parent().unwrap_or(Path::new("")))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(Some(ThemeFile {
+        path,
+        themes: themes.into_iter().map(Arc::new).collect(),
+    }))
+}
+
+pub struct ConfigFile {
+    pub path: PathBuf,
+    pub table: toml::Table,
+}
+
+impl ConfigFile {
+    /// Loads `explicit` if given (missing file is an error), otherwise the
+    /// default XDG location (missing file is silently ignored).
+    pub fn load(explicit: Option<&Path>) -> Result<Option<Self>, String> {
+        let (path, required) = match explicit {
+            Some(p) => (p.to_path_buf(), true),
+            None => match default_config_path() {
+                Some(p) => (p, false),
+                None => return Ok(None),
+            },
+        };
+
+        let text = match std::fs::read_to_string(&path) {
+            Ok(t) => t,
+            Err(e) if !required && e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("failed to read {}: {}", path.display(), e)),
+        };
+
+        let table = text
+            .parse::<toml::Table>()
+            .map_err(|e| format!("{}: {}", path.display(), e.message()))?;
+
+        Ok(Some(Self { path, table }))
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Origin {
+    Env(String),
+    File { path: PathBuf, key: String },
+}
+
+/// Command line with environment and config file values merged in (ahead of
+/// the user's own arguments), plus a record of where each non-CLI value came
+/// from (for error messages).
+pub struct Layered {
+    pub argv: Vec<OsString>,
+    origins: Vec<(String, Origin)>,
+}
+
+impl Layered {
+    /// Name used in validation errors: `--fps` for CLI/default values,
+    /// `COSMOSTRIX_FPS` for environment values, `PATH: fps` for file values.
+    pub fn label(&self, long: &str) -> String {
+        match self.origins.iter().find(|(l, _)| l == long).map(|(_, o)| o) {
+            Some(Origin::Env(var)) => var.clone(),
+            Some(Origin::File { path, key }) => format!("{}: {}", path.display(), key),
+            None => format!("--{}", long),
+        }
+    }
+
+    /// `"LABEL: "` for values that did not come from the command line, so
+    /// parse errors can point at the file or variable; empty otherwise.
+    pub fn context(&self, long: &str) -> String {
+        if self.origins.iter().any(|(l, _)| l == long) {
+            format!("{}: ", self.label(long))
+        } else {
+            String::new()
+        }
+    }
+}
+
+pub fn env_var_name(long: &str) -> String {
+    format!(
+        "{}{}",
+        ENV_PREFIX,
+        long.replace('-', "_").to_ascii_uppercase()
+    )
+}
+
+fn is_layerable(arg: &clap::Arg) -> bool {
+    arg.get_long().is_some()
+        && !matches!(arg.get_id().as_str(), "config" | "profile")
+        && arg.get_help_heading() != Some("HELP")
+}
+
+fn toml_to_arg_value(v: &toml::Value) -> Option<String> {
+    match v {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        toml::Value::Array(items) => {
+            let parts: Option<Vec<String>> = items
+                .iter()
+                .map(|i| match i {
+                    toml::Value::Array(_) | toml::Value::Table(_) => None,
+                    other => toml_to_arg_value(other),
+                })
+                .collect();
+            parts.map(|p| p.join(","))
+        }
+        _ => None,
+    }
+}
+
+/// On/off options, which take `--flag`, `--flag=true` or `--flag=false`.
+fn is_switch(arg: &clap::Arg) -> bool {
+    let values = arg.get_possible_values();
+    values.len() == 2 && values[0].get_name() == "true" && values[1].get_name() == "false"
+}
+
+fn push_layer_value(
+    cmd: &Command,
+    arg: &clap::Arg,
+    value: &str,
+    what: &str,
+    out: &mut Vec<OsString>,
+) -> Result<(), String> {
+    let long = arg.get_long().unwrap_or_default();
+    let bin = cmd.get_name().to_string();
+
+    let value = if is_switch(arg) {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => "true",
+            "false" | "0" | "no" | "off" | "" => "false",
+            _ => return Err(format!("{}: expected true or false, got {:?}", what, value)),
+        }
+    } else {
+        value
+    };
+    let flag = OsString::from(format!("--{}={}", long, value));
+
+    if let Err(e) = cmd
+        .clone()
+        .try_get_matches_from([OsString::from(bin), flag.clone()])
+    {
+        let rendered = e.to_string();
+        let first = rendered.lines().next().unwrap_or_default();
+        let reason = first.rsplit("': ").next().unwrap_or(first);
+        let reason = reason.trim_start_matches("error: ");
+        return Err(format!("{}: invalid value {:?} ({})", what, value, reason));
+    }
+
+    out.push(flag);
+    Ok(())
+}
+
+fn check_keys(
+    cmd: &Command,
+    table: &toml::Table,
+    path: &Path,
+    prefix: &str,
+    extra: &[&str],
+) -> Result<(), String> {
+    for key in table.keys() {
+        let long = key.replace('_', "-");
+        let known = extra.contains(&long.as_str())
+            || cmd
+                .get_arguments()
+                .any(|a| is_layerable(a) && a.get_long() == Some(long.as_str()));
+        if !known {
+            return Err(format!(
+                "{}: unknown key `{}{}` (see cosmostrix --help)",
+                path.display(),
+                prefix,
+                key
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn find_key<'a>(table: &'a toml::Table, long: &str) -> Option<(&'a String, &'a toml::Value)> {
+    table
+        .iter()
+        .find(|(k, v)| !v.is_table() && k.replace('_', "-") == long)
+}
+
+impl ConfigFile {
+    /// `[profile.NAME]` tables in file order, with their optional
+    /// `description` key.
+    pub fn profiles(&self) -> Vec<(&str, Option<&str>)> {
+        let Some(toml::Value::Table(profiles)) = self.table.get("profile") else {
+            return Vec::new();
+        };
+        profiles
+            .iter()
+            .filter_map(|(name, v)| {
+                let t = v.as_table()?;
+                Some((name.as_str(), t.get("description").and_then(|d| d.as_str())))
+            })
+            .collect()
+    }
+
+    fn profile(&self, name: &str) -> Result<&toml::Table, String> {
+        self.table
+            .get("profile")
+            .and_then(|p| p.get(name))
+            .and_then(|p| p.as_table())
+            .ok_or_else(|| {
+                let names: Vec<&str> = self.profiles().iter().map(|(n, _)| *n).collect();
+                format!(
+                    "{}: no profile named `{}` (available: {})",
+                    self.path.display(),
+                    name,
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                )
+            })
+    }
+
+    /// Profile named by `default-profile` at the top level, if any.
+    pub fn default_profile(&self) -> Result<Option<&str>, String> {
+        match find_key(&self.table, "default-profile") {
+            None => Ok(None),
+            Some((_, toml::Value::String(s))) => Ok(Some(s.as_str())),
+            Some((key, _)) => Err(format!(
+                "{}: {}: expected a profile name",
+                self.path.display(),
+                key
+            )),
+        }
+    }
+}
+
+/// Merges a selected profile, environment variables and config file values
+/// underneath the command line. Precedence: CLI > profile > environment >
+/// file > built-in default.
+pub fn layer_args(
+    cmd: &Command,
+    matches: &ArgMatches,
+    argv: &[OsString],
+    file: Option<&ConfigFile>,
+    profile: Option<&str>,
+) -> Result<Layered, String> {
+    let mut out: Vec<OsString> = argv.iter().take(1).cloned().collect();
+    let mut origins: Vec<(String, Origin)> = Vec::new();
+
+    if let Some(file) = file {
+        check_keys(
+            cmd,
+            &file.table,
+            &file.path,
+            "",
+            &["profile", "default-profile"],
+        )?;
+        if let Some(p) = file.table.get("profile") {
+            let Some(profiles) = p.as_table().filter(|t| t.values().all(|v| v.is_table())) else {
+                return Err(format!(
+                    "{}: profiles must be tables, e.g. [profile.office]",
+                    file.path.display()
+                ));
+            };
+            for (name, t) in profiles {
+                if let Some(t) = t.as_table() {
+                    let prefix = format!("profile.{}.", name);
+                    check_keys(cmd, t, &file.path, &prefix, &["description"])?;
+                }
+            }
+        }
+    }
+
+    let profile = match (profile, file) {
+        (Some(name), Some(file)) => Some((name, file.profile(name)?)),
+        (Some(name), None) => {
+            return Err(format!(
+                "no config file to load profile `{}` from (see --config)",
+                name
+            ))
+        }
+        (None, _) => None,
+    };
+
+    for arg in cmd.get_arguments().filter(|a| is_layerable(a)) {
+        let long = arg.get_long().unwrap_or_default();
+        let id = arg.get_id().as_str();
+        if matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine) | Some(ValueSource::EnvVariable)
+        ) {
+            continue;
+        }
+
+        let from_profile = profile.and_then(|(name, t)| {
+            find_key(t, long).map(|(k, v)| (format!("profile.{}.{}", name, k), v))
+        });
+        if let (Some((key, value)), Some(file)) = (from_profile, file) {
+            let what = format!("{}: {}", file.path.display(), key);
+            let value = toml_to_arg_value(value)
+                .ok_or_else(|| format!("{}: expected a string, number or boolean", what))?;
+            push_layer_value(cmd, arg, &value, &what, &mut out)?;
+            origins.push((
+                long.to_string(),
+                Origin::File {
+                    path: file.path.clone(),
+                    key,
+                },
+            ));
+            continue;
+        }
+
+        let var = env_var_name(long);
+        if let Some(raw) = env::var_os(&var) {
+            let value = raw
+                .into_string()
+                .map_err(|_| format!("{}: value is not valid unicode", var))?;
+            push_layer_value(cmd, arg, &value, &var, &mut out)?;
+            origins.push((long.to_string(), Origin::Env(var)));
+            continue;
+        }
+
+        let Some(file) = file else {
+            continue;
+        };
+        let Some((key, value)) = find_key(&file.table, long) else {
+            continue;
+        };
+
+        let what = format!("{}: {}", file.path.display(), key);
+        let value = toml_to_arg_value(value)
+            .ok_or_else(|| format!("{}: expected a string, number or boolean", what))?;
+        push_layer_value(cmd, arg, &value, &what, &mut out)?;
+        origins.push((
+            long.to_string(),
+            Origin::File {
+                path: file.path.clone(),
+                key: key.clone(),
+            },
+        ));
+    }
+
+    out.extend(argv.iter().skip(1).cloned());
+    Ok(Layered { argv: out, origins })
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+    use crate::config::Args;
+
+    fn file(text: &str) -> ConfigFile {
+        ConfigFile {
+            path: PathBuf::from("test.toml"),
+            table: text.parse().unwrap(),
+        }
+    }
+
+    fn layered(argv: &[&str], text: &str) -> Result<Layered, String> {
+        let cmd = Args::command();
+        let argv: Vec<OsString> = argv.iter().map(OsString::from).collect();
+        let matches = cmd.clone().get_matches_from(argv.clone());
+        layer_args(&cmd, &matches, &argv, Some(&file(text)), None)
+    }
+
+    #[test]
+    fn file_values_fill_in_below_cli() {
+        let l = layered(
+            &["cosmostrix", "--fps", "30"],
+            "fps = 90\ncolor = \"fire\"\nglitchms = [200, 500]\nfullwidth = true\n",
+        )
+        .unwrap();
+        let argv: Vec<String> = l
+            .argv
+            .iter()
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect();
+        assert!(argv.contains(&"--color=fire".to_string()));
+        assert!(argv.contains(&"--glitchms=200,500".to_string()));
+        assert!(argv.contains(&"--fullwidth=true".to_string()));
+        assert_eq!(argv.last().map(String::as_str), Some("30"));
+        assert!(!argv.iter().any(|a| a == "--fps=90"));
+        assert_eq!(l.label("fps"), "--fps");
+        assert_eq!(l.label("color"), "test.toml: color");
+    }
+
+    #[test]
+    fn cli_can_switch_off_flags_set_in_the_file() {
+        let text = "fullwidth = true\nscreensaver = \"on\"\nperf-stats = 1\n";
+        let parse = |argv: &[&str]| {
+            let l = layered(argv, text).unwrap();
+            Args::from_arg_matches(&Args::command().get_matches_from(l.argv)).unwrap()
+        };
+        let args = parse(&["cosmostrix"]);
+        assert!(args.fullwidth && args.screensaver && args.perf_stats);
+
+        let args = parse(&["cosmostrix", "--fullwidth=false", "-s", "false"]);
+        assert!(!args.fullwidth && !args.screensaver && args.perf_stats);
+
+        let err = layered(&["cosmostrix"], "fullwidth = \"maybe\"\n")
+            .err()
+            .unwrap();
+        assert!(err.contains("expected true or false"), "{}", err);
+    }
+
+    #[test]
+    fn file_errors_name_the_file_and_key() {
+        let err = layered(&["cosmostrix"], "fps = \"fast\"\n").err().unwrap();
+        assert!(err.starts_with("test.toml: fps"), "{}", err);
+
+        let err = layered(&["cosmostrix"], "colour = \"red\"\n")
+            .err()
+            .unwrap();
+        assert!(err.contains("unknown key `colour`"), "{}", err);
+    }
+
+    #[test]
+    fn profile_sits_between_cli_and_file() {
+        let cf = file(
+            "fps = 90\ncolor = \"fire\"\n\n[profile.demo]\ndescription = \"Demo\"\ncolor = \"neon\"\nspeed = 20\n",
+        );
+        assert_eq!(cf.profiles(), vec![("demo", Some("Demo"))]);
+
+        let cmd = Args::command();
+        let argv: Vec<OsString> = ["cosmostrix", "--speed", "4"]
+            .iter()
+            .map(OsString::from)
+            .collect();
+        let matches = cmd.clone().get_matches_from(argv.clone());
+        let l = layer_args(&cmd, &matches, &argv, Some(&cf), Some("demo")).unwrap();
+        assert_eq!(l.label("color"), "test.toml: profile.demo.color");
+        assert_eq!(l.label("fps"), "test.toml: fps");
+        assert_eq!(l.label("speed"), "--speed");
+
+        let err = layer_args(&cmd, &matches, &argv, Some(&cf), Some("office"))
+            .err()
+            .unwrap();
+        assert!(err.contains("available: demo"), "{}", err);
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Color-vision deficiency simulation (`--simulate-cvd`), for checking that
+//! a theme's steps stay distinguishable.
+
+use crate::oklab::{from_linear, to_linear};
+
+/// A kind of dichromacy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cvd {
+    /// No working long-wavelength (red) cones.
+    Protan,
+    /// No working medium-wavelength (green) cones; the most common kind.
+    Deutan,
+    /// No working short-wavelength (blue) cones.
+    Tritan,
+}
+
+impl Cvd {
+    /// Machado, Oliveira and Fernandes (2009), severity 1, for linear sRGB.
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Cvd::Protan => [
+                [0.152_286, 1.052_583, -0.204_868],
+                [0.114_503, 0.786_281, 0.099_216],
+                [-0.003_882, -0.048_116, 1.051_998],
+            ],
+            Cvd::Deutan => [
+                [0.367_322, 0.860_646, -0.227_968],
+                [0.280_085, 0.672_501, 0.047_413],
+                [-0.011_820, 0.042_940, 0.968_881],
+            ],
+            Cvd::Tritan => [
+                [1.255_528, -0.076_749, -0.178_779],
+                [-0.078_411, 0.930_809, 0.147_602],
+                [0.004_733, 0.691_367, 0.303_900],
+            ],
+        }
+    }
+
+    /// How `rgb` looks with this deficiency.
+    pub fn simulate(self, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        let lin = [to_linear(r), to_linear(g), to_linear(b)];
+        let out = self.matrix().map(|row| {
+            let v = row[0] * lin[0] + row[1] * lin[1] + row[2] * lin[2];
+            (from_linear(v.clamp(0.0, 1.0)) * 255.0).round() as u8
+        });
+        (out[0], out[1], out[2])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::oklab::Oklab;
+    use crate::palette::{build_palette, color_to_rgb};
+    use crate::runtime::{ColorMode, ColorScheme};
+
+    #[test]
+    fn colorblind_safe_themes_stay_ordered_under_simulation() {
+        let dist = |a, b| Oklab::from_rgb(a).distance2(Oklab::from_rgb(b));
+        let (red, green) = ((200, 40, 40), (40, 160, 40));
+        assert!(
+            dist(Cvd::Deutan.simulate(red), Cvd::Deutan.simulate(green)) < dist(red, green) / 4.0
+        );
+        assert_eq!(Cvd::Protan.simulate((255, 255, 255)), (255, 255, 255));
+
+        for scheme in [
+            ColorScheme::Viridis,
+            ColorScheme::Cividis,
+            ColorScheme::Magma,
+        ] {
+            let colors = build_palette(&scheme, ColorMode::TrueColor, false).colors;
+            for cvd in [Cvd::Protan, Cvd::Deutan, Cvd::Tritan] {
+                let l: Vec<f32> = colors
+                    .iter()
+                    .map(|&c| Oklab::from_rgb(cvd.simulate(color_to_rgb(c).unwrap())).l)
+                    .collect();
+                assert!(
+                    l.windows(2).all(|w| w[1] - w[0] > 0.02),
+                    "{:?} as {:?}: {:?}",
+                    scheme,
+                    cvd,
+                    l
+                );
+            }
+        }
+    }
+}
@@ -1,10 +1,14 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! Individual rain streaks.
+
 use std::time::{Duration, Instant};
 
 use crate::cloud::{CharLoc, DrawCtx};
 use crate::frame::Frame;
 
+/// One falling streak: a head that crawls down a column and a tail that
+/// erases behind it. Droplets are pooled and recycled by [`crate::Cloud`].
 #[derive(Clone, Debug)]
 pub struct Droplet {
     pub is_alive: bool,
@@ -26,11 +30,19 @@ pub struct Droplet {
     pub advance_remainder: f32,
 
     pub last_time: Option<Instant>,
+    pub birth_time: Option<Instant>,
     pub head_stop_time: Option<Instant>,
     pub time_to_linger: Duration,
 }
 
+impl Default for Droplet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Droplet {
+    /// An inactive droplet.
     pub fn new() -> Self {
         Self {
             is_alive: false,
@@ -49,28 +61,50 @@ impl Droplet {
             advance_remainder: 0.0,
 
             last_time: None,
+            birth_time: None,
             head_stop_time: None,
             time_to_linger: Duration::from_millis(0),
         }
     }
 
+    /// Starts the droplet at `now`.
     pub fn activate(&mut self, now: Instant) {
         self.is_alive = true;
         self.is_head_crawling = true;
         self.is_tail_crawling = true;
         self.advance_remainder = 0.0;
         self.last_time = Some(now);
+        self.birth_time = Some(now);
     }
 
+    /// Shifts the droplet's clocks forward, e.g. after a pause.
     pub fn increment_time(&mut self, delta: Duration) {
         if let Some(t) = self.last_time.as_mut() {
             *t += delta;
         }
+        if let Some(t) = self.birth_time.as_mut() {
+            *t += delta;
+        }
         if let Some(t) = self.head_stop_time.as_mut() {
             *t += delta;
         }
     }
 
+    /// How far the droplet is through its fall, from 0 (just started) to 1
+    /// (the tail has had time to clear the column).
+    pub fn age(&self, now: Instant) -> f32 {
+        let Some(born) = self.birth_time else {
+            return 0.0;
+        };
+        let life = (self.end_line as f32 + self.length as f32 + 1.0) / self.chars_per_sec;
+        if !(life.is_finite() && life > 0.0) {
+            return 0.0;
+        }
+        (now.saturating_duration_since(born).as_secs_f32() / life).min(1.0)
+    }
+
+    /// Moves head and tail for the time elapsed until `now`. Returns true
+    /// once the tail has cleared enough of the column for another droplet.
     pub fn advance(&mut self, now: Instant, lines: u16) -> bool {
         let Some(last) = self.last_time else {
             self.last_time = Some(now);
@@ -153,6 +187,7 @@ impl Droplet {
         false
     }
 
+    /// Renders the droplet's changed cells into `frame`.
     pub fn draw(
         &mut self,
         ctx: &DrawCtx<'_>,
@@ -171,6 +206,7 @@ impl Droplet {
             start_line = tp.saturating_add(1);
         }
 
+        let age = self.age(now);
         for line in start_line..=self.head_put_line {
             if line >= ctx.lines {
                 break;
@@ -191,7 +227,7 @@ impl Droplet {
                 && line < self.head_cur_line
                 && !is_glitched
                 && line != self.end_line
-                && !ctx.shading_distance
+                && !ctx.recolors_body()
                 && !draw_everything
             {
                 continue;
@@ -204,7 +240,13 @@ impl Droplet {
                 loc,
                 now,
                 self.head_put_line,
+                if self.is_head_crawling {
+                    self.advance_remainder
+                } else {
+                    0.0
+                },
                 self.length,
+                age,
             );
 
             frame.set(
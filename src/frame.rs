@@ -1,7 +1,12 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! Off-screen cell grid with change tracking.
+
 use crate::cell::Cell;
 
+/// A `width` x `height` grid of [`Cell`]s, row-major. Writes through
+/// [`Frame::set`] record which cells changed so a renderer only has to
+/// redraw those; call [`Frame::clear_dirty`] once they are on screen.
 #[derive(Clone, Debug)]
 pub struct Frame {
     pub width: u16,
@@ -16,6 +21,7 @@ pub struct Frame {
 }
 
 impl Frame {
+    /// A blank frame filled with `bg`, marked fully dirty.
     pub fn new(width: u16, height: u16, bg: Option<crossterm::style::Color>) -> Self {
         let len = width as usize * height as usize;
         let blank = Cell::blank_with_bg(bg);
@@ -33,6 +39,7 @@ impl Frame {
         }
     }
 
+    /// Blanks every cell in O(1) and marks the whole frame dirty.
     pub fn clear_with_bg(&mut self, bg: Option<crossterm::style::Color>) {
         self.blank = Cell::blank_with_bg(bg);
         self.gen = self.gen.wrapping_add(1);
@@ -44,15 +51,18 @@ impl Frame {
         self.dirty.clear();
     }
 
+    /// True when every cell must be redrawn (after `new` or `clear_with_bg`).
     pub fn is_dirty_all(&self) -> bool {
         self.dirty_all
     }
 
+    /// Row-major indices changed since the last [`Frame::clear_dirty`]; only
+    /// meaningful when [`Frame::is_dirty_all`] is false.
     pub fn dirty_indices(&self) -> &[usize] {
         &self.dirty
     }
 
-    #[allow(dead_code)]
+    /// Sorts [`Frame::dirty_indices`] into row-major order.
     pub fn sort_dirty(&mut self) {
         if self.dirty_all || self.dirty.len() <= 1 {
             return;
@@ -60,6 +70,7 @@ impl Frame {
         self.dirty.sort_unstable();
     }
 
+    /// Forgets all pending changes.
     pub fn clear_dirty(&mut self) {
         if self.dirty_all {
             self.dirty_all = false;
@@ -76,6 +87,7 @@ impl Frame {
         self.dirty.clear();
     }
 
+    /// Row-major index of `(x, y)`, or `None` if out of bounds.
     pub fn index(&self, x: u16, y: u16) -> Option<usize> {
         if x >= self.width || y >= self.height {
             return None;
@@ -83,7 +95,7 @@ impl Frame {
         Some(y as usize * self.width as usize + x as usize)
     }
 
-    #[allow(dead_code)]
+    /// The cell at `(x, y)`, or `None` if out of bounds.
     pub fn get(&self, x: u16, y: u16) -> Option<&Cell> {
         self.index(x, y).map(|i| {
             if self.cell_gen.get(i).copied() == Some(self.gen) {
@@ -94,6 +106,7 @@ impl Frame {
         })
     }
 
+    /// The cell at a row-major index.
     pub fn cell_at_index(&self, i: usize) -> Cell {
         if self.cell_gen.get(i).copied() == Some(self.gen) {
             self.cells[i]
@@ -102,6 +115,8 @@ impl Frame {
         }
     }
 
+    /// Writes a cell, marking it dirty if it changed. Out-of-bounds writes
+    /// are ignored.
     pub fn set(&mut self, x: u16, y: u16, cell: Cell) {
         if let Some(i) = self.index(x, y) {
             let cur = if self.cell_gen.get(i).copied() == Some(self.gen) {
@@ -0,0 +1,250 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Animated GIF export (`--export-gif`).
+//!
+//! The global color table is built from the theme [`Palette`] plus the
+//! default colors, so the rain needs no quantization; anything else (message
+//! box, custom colors) is mapped to the nearest entry. Each GIF frame only
+//! covers the bounding box of the cells that changed, taken from the
+//! [`Frame`] dirty list, and frames closer together than GIF's practical
+//! minimum delay are merged.
+
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Result, Write};
+use std::time::Duration;
+
+use gif::{DisposalMethod, Encoder, Repeat};
+
+use crate::frame::Frame;
+use crate::palette::{color_to_rgb, Palette};
+use crate::raster::{rasterize_region, DEFAULT_BG, DEFAULT_FG};
+
+/// Browsers slow down delays below 2 centiseconds, so merge shorter frames.
+const MIN_DELAY_S: f64 = 0.02;
+
+/// Changed cells as `(x0, y0, x1, y1)`, inclusive.
+type Rect = (u16, u16, u16, u16);
+
+fn union(a: Option<Rect>, b: Rect) -> Rect {
+    match a {
+        None => b,
+        Some(a) => (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)),
+    }
+}
+
+fn dirty_rect(frame: &Frame) -> Option<Rect> {
+    if frame.width == 0 || frame.height == 0 {
+        return None;
+    }
+    if frame.is_dirty_all() {
+        return Some((0, 0, frame.width - 1, frame.height - 1));
+    }
+    let w = frame.width as usize;
+    frame.dirty_indices().iter().fold(None, |acc, &i| {
+        let (x, y) = ((i % w) as u16, (i / w) as u16);
+        Some(union(acc, (x, y, x, y)))
+    })
+}
+
+fn to_io(e: gif::EncodingError) -> Error {
+    match e {
+        gif::EncodingError::Io(e) => e,
+        other => Error::new(ErrorKind::Other, other),
+    }
+}
+
+/// Streams frames of one size into an animated, looping GIF.
+pub struct GifExporter<W: Write> {
+    enc: Encoder<W>,
+    colors: Vec<(u8, u8, u8)>,
+    lookup: HashMap<(u8, u8, u8), u8>,
+    cell_w: u32,
+    cell_h: u32,
+    /// Encoded but not yet written: its delay is only known once the next
+    /// frame is ready.
+    held: Option<gif::Frame<'static>>,
+    held_s: f64,
+    /// Rounding left over from earlier delays, so timing does not drift.
+    carry_s: f64,
+    pending: Option<Rect>,
+    last_dt_s: f64,
+}
+
+impl<W: Write> GifExporter<W> {
+    /// Starts a GIF for `cols` x `lines` cells of `cell_w` x `cell_h` pixels.
+    pub fn new(
+        out: W,
+        cols: u16,
+        lines: u16,
+        cell_w: u32,
+        cell_h: u32,
+        palette: &Palette,
+    ) -> Result<Self> {
+        let mut colors = vec![DEFAULT_BG, DEFAULT_FG];
+        let theme = palette.bg.into_iter().chain(palette.colors.iter().copied());
+        for rgb in theme.filter_map(color_to_rgb) {
+            if !colors.contains(&rgb) && colors.len() < 256 {
+                colors.push(rgb);
+            }
+        }
+        let table: Vec<u8> = colors.iter().flat_map(|&(r, g, b)| [r, g, b]).collect();
+
+        let (w, h) = (cols as u32 * cell_w, lines as u32 * cell_h);
+        if w > u16::MAX as u32 || h > u16::MAX as u32 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("GIF too large: {}x{} px (max 65535)", w, h),
+            ));
+        }
+        let mut enc = Encoder::new(out, w as u16, h as u16, &table).map_err(to_io)?;
+        enc.set_repeat(Repeat::Infinite).map_err(to_io)?;
+
+        Ok(Self {
+            enc,
+            lookup: colors
+                .iter()
+                .enumerate()
+                .map(|(i, &c)| (c, i as u8))
+                .collect(),
+            colors,
+            cell_w,
+            cell_h,
+            held: None,
+            held_s: 0.0,
+            carry_s: 0.0,
+            pending: None,
+            last_dt_s: 0.0,
+        })
+    }
+
+    fn index_of(&mut self, rgb: (u8, u8, u8)) -> u8 {
+        if let Some(&i) = self.lookup.get(&rgb) {
+            return i;
+        }
+        let d2 = |c: &(u8, u8, u8)| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(c.0, rgb.0) + d(c.1, rgb.1) + d(c.2, rgb.2)
+        };
+        let i = (0..self.colors.len())
+            .min_by_key(|&i| d2(&self.colors[i]))
+            .unwrap_or(0) as u8;
+        self.lookup.insert(rgb, i);
+        i
+    }
+
+    fn write_held(&mut self, extra_s: f64) -> Result<()> {
+        if let Some(mut f) = self.held.take() {
+            let total = self.held_s + extra_s + self.carry_s;
+            let cs = (total * 100.0).round().max(MIN_DELAY_S * 100.0);
+            self.carry_s = total - cs / 100.0;
+            f.delay = cs.min(u16::MAX as f64) as u16;
+            self.enc.write_frame(&f).map_err(to_io)?;
+        }
+        self.held_s = 0.0;
+        Ok(())
+    }
+
+    /// Adds the state of `frame`, `dt` after the previous one. Call before
+    /// [`Frame::clear_dirty`]: only the dirty cells are encoded.
+    pub fn push(&mut self, frame: &Frame, dt: Duration) -> Result<()> {
+        let dt = dt.as_secs_f64();
+        self.last_dt_s = dt;
+        if self.held.is_some() {
+            self.held_s += dt;
+        }
+        if let Some(r) = dirty_rect(frame) {
+            self.pending = Some(union(self.pending, r));
+        }
+        let due = self.held.is_none() || self.held_s + self.carry_s >= MIN_DELAY_S;
+        let Some((x0, y0, x1, y1)) = self.pending.filter(|_| due) else {
+            return Ok(());
+        };
+        self.write_held(0.0)?;
+        self.pending = None;
+
+        let img = rasterize_region(
+            frame,
+            self.cell_w,
+            self.cell_h,
+            x0,
+            y0,
+            x1 - x0 + 1,
+            y1 - y0 + 1,
+        );
+        let buffer: Vec<u8> = img
+            .rgb
+            .chunks_exact(3)
+            .map(|p| self.index_of((p[0], p[1], p[2])))
+            .collect();
+        self.held = Some(gif::Frame {
+            left: (x0 as u32 * self.cell_w) as u16,
+            top: (y0 as u32 * self.cell_h) as u16,
+            width: img.width as u16,
+            height: img.height as u16,
+            dispose: DisposalMethod::Keep,
+            buffer: buffer.into(),
+            ..gif::Frame::default()
+        });
+        Ok(())
+    }
+
+    /// Writes the last frame and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        let last = self.last_dt_s;
+        self.write_held(last)?;
+        self.enc.into_inner()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crossterm::style::Color;
+
+    use crate::cell::Cell;
+
+    #[test]
+    fn encodes_only_changed_regions() {
+        let palette = Palette {
+            colors: vec![Color::Rgb { r: 0, g: 255, b: 0 }],
+            bg: None,
+        };
+        let mut frame = Frame::new(10, 4, None);
+        let mut gif = GifExporter::new(Vec::new(), 10, 4, 2, 3, &palette).unwrap();
+        let step = Duration::from_millis(40);
+
+        gif.push(&frame, step).unwrap();
+        frame.clear_dirty();
+        frame.set(
+            3,
+            2,
+            Cell {
+                ch: '#',
+                fg: Some(Color::Rgb { r: 0, g: 250, b: 0 }),
+                bg: None,
+                bold: false,
+            },
+        );
+        gif.push(&frame, step).unwrap();
+        frame.clear_dirty();
+        // Nothing changed: extends the previous frame instead of adding one.
+        gif.push(&frame, step).unwrap();
+        let bytes = gif.finish().unwrap();
+
+        let mut opts = gif::DecodeOptions::new();
+        opts.set_color_output(gif::ColorOutput::Indexed);
+        let mut dec = opts.read_info(bytes.as_slice()).unwrap();
+        let first = dec.read_next_frame().unwrap().unwrap().clone();
+        assert_eq!((first.width, first.height, first.delay), (20, 12, 4));
+        let second = dec.read_next_frame().unwrap().unwrap().clone();
+        assert_eq!(
+            (second.left, second.top, second.width, second.height),
+            (6, 6, 2, 3)
+        );
+        assert_eq!(second.delay, 8);
+        // The off-palette green snapped to the theme color (index 2).
+        assert!(second.buffer.contains(&2));
+        assert!(dec.read_next_frame().unwrap().is_none());
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Matrix-style rain simulation for terminals.
+//!
+//! The `cosmostrix` binary is a thin client of this crate; the same pieces can
+//! be embedded in other TUIs. A [`Cloud`] owns the droplets and renders them
+//! into a [`Frame`], a grid of [`Cell`]s that tracks which cells changed since
+//! the last draw. A [`Backend`] puts frames on screen: [`Terminal`] drives
+//! the real terminal through crossterm, [`MemoryBackend`] records the output
+//! for tests. [`run_loop`] is the binary's interactive loop (keys, pacing,
+//! resizing) and runs against any backend.
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//!
+//! use cosmostrix::{
+//!     build_chars, Charset, Cloud, ColorMode, ColorScheme, Frame, ShadingMode,
+//! };
+//!
+//! let mut cloud = Cloud::builder()
+//!     .color_mode(ColorMode::TrueColor)
+//!     .shading_mode(ShadingMode::DistanceFromHead)
+//!     .color_scheme(ColorScheme::Green)
+//!     .seed(42)
+//!     .build();
+//! cloud.init_chars(build_chars(Charset::MATRIX, &[], false));
+//!
+//! let start = Instant::now();
+//! cloud.reset_at(80, 24, start);
+//! let mut frame = Frame::new(80, 24, cloud.palette().bg);
+//! for i in 1..=60 {
+//!     cloud.rain_at(&mut frame, start + Duration::from_millis(16) * i);
+//!     // draw `frame.dirty_indices()` somewhere, then:
+//!     frame.clear_dirty();
+//! }
+//! ```
+//!
+//! Everything re-exported at the crate root is the supported API and follows
+//! semver; modules hidden from these docs are helpers for the binary.
+
+mod cell;
+mod cloud;
+mod cvd;
+mod droplet;
+mod frame;
+mod motion;
+mod oklab;
+mod palette;
+mod theme_import;
+
+// Helpers for the `cosmostrix` binary. They are not part of the supported
+// API and may change in any release.
+#[doc(hidden)]
+pub mod ansi;
+#[doc(hidden)]
+pub mod cast;
+#[doc(hidden)]
+pub mod charset;
+#[doc(hidden)]
+pub mod gif_export;
+#[doc(hidden)]
+pub mod raster;
+#[doc(hidden)]
+pub mod run;
+#[doc(hidden)]
+pub mod runtime;
+#[doc(hidden)]
+pub mod script;
+#[doc(hidden)]
+pub mod terminal;
+#[doc(hidden)]
+pub mod theme;
+
+pub use crossterm::style::Color;
+
+pub use cell::Cell;
+pub use charset::{
+    build_chars, charset_from_str, parse_char_ranges, parse_literal_chars, parse_user_hex_chars,
+    weighted_chars_from_str, Charset,
+};
+pub use cloud::{Cloud, CloudBuilder};
+pub use cvd::Cvd;
+pub use droplet::Droplet;
+pub use frame::Frame;
+pub use palette::{build_adjusted_palette, build_palette, ColorAdjust, Palette};
+pub use run::{run_loop, ColorCycle, PerfStats, RunOptions};
+pub use runtime::{
+    all_color_schemes, parse_color_scheme, parse_color_scheme_with, BoldMode, ColorMode,
+    ColorScheme, ShadingMode, SmoothShading,
+};
+pub use terminal::{Backend, MemoryBackend, Terminal};
+pub use theme::CustomTheme;
@@ -1,16 +1,12 @@
 // Copyright (c) 2026 rezky_nightky
 
-mod cell;
-mod charset;
-mod cloud;
 mod config;
-mod droplet;
-mod frame;
-mod palette;
-mod runtime;
-mod terminal;
+mod config_file;
+mod play;
 
 use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::time::{Duration, Instant};
 
 #[cfg(target_os = "linux")]
@@ -30,7 +26,6 @@ use clap::builder::styling::{Effects as ClapEffects, Style as ClapStyle};
 use clap::builder::Styles as ClapStyles;
 use clap::parser::ValueSource;
 use clap::{CommandFactory, FromArgMatches};
-use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 
 #[cfg(unix)]
 use signal_hook::consts::{SIGCONT, SIGHUP, SIGINT, SIGSTOP, SIGTERM, SIGTSTP};
@@ -40,15 +35,27 @@ use signal_hook::iterator::Signals;
 #[cfg(unix)]
 use signal_hook::low_level;
 
-use crate::charset::{build_chars, charset_from_str, parse_user_hex_chars, Charset};
-use crate::cloud::Cloud;
+use cosmostrix::cast::{parse_cast, CastWriter};
+use cosmostrix::charset::{charset_expr_groups, normalize_charset_preset_name};
+use cosmostrix::gif_export::GifExporter;
+use cosmostrix::raster::{image_size, rasterize};
+use cosmostrix::run::{effective_density, run_loop, ColorCycle, RunOptions};
+use cosmostrix::runtime::SHADING_MODES;
+use cosmostrix::script::is_wide;
+use cosmostrix::terminal::{restore_terminal_best_effort, Backend, Terminal};
+use cosmostrix::{
+    all_color_schemes, parse_char_ranges, parse_color_scheme_with, parse_literal_chars,
+    weighted_chars_from_str, BoldMode, Charset, Cloud, ColorAdjust, ColorMode, ColorScheme, Cvd,
+    Frame, SmoothShading,
+};
+
 use crate::config::{
     color_enabled_stdout, default_params_usage_for_help, print_help_detail, print_list_charsets,
-    print_list_colors, Args, ColorBg,
+    print_list_colors, print_list_profiles, print_list_scripts, reduced_motion_from_env, Args,
+    ColorBg, Command, CvdArg, ExportFormat, PlayArgs, SmoothShadingArg,
 };
-use crate::frame::Frame;
-use crate::runtime::{BoldMode, ColorMode, ColorScheme, ShadingMode};
-use crate::terminal::{restore_terminal_best_effort, Terminal};
+use crate::config_file::{layer_args, ConfigFile, ThemeFile};
+use crate::play::{play, PlayOptions};
 
 const HELP_TEMPLATE_PLAIN: &str = "\
 {before-help}{about-with-newline}
@@ -195,6 +202,15 @@ fn require_u8_range(name: &str, v: u8, min: u8, max: u8) -> u8 {
     v
 }
 
+fn require_u32_range(name: &str, v: u32, min: u32, max: u32) -> u32 {
+    if v < min || v > max {
+        restore_terminal_best_effort();
+        eprintln!("failed to apply {} {} (min {} max {})", name, v, min, max);
+        std::process::exit(1);
+    }
+    v
+}
+
 fn require_u16_range(name: &str, v: u16, min: u16, max: u16) -> u16 {
     if v < min || v > max {
         restore_terminal_best_effort();
@@ -238,7 +254,7 @@ fn detect_color_mode_auto() -> ColorMode {
     ColorMode::Color16
 }
 
-fn detect_color_mode(args: &Args) -> ColorMode {
+fn detect_color_mode(args: &Args, name: &str) -> ColorMode {
     if let Some(m) = args.colormode {
         return match m {
             0 => ColorMode::Mono,
@@ -246,7 +262,7 @@ fn detect_color_mode(args: &Args) -> ColorMode {
             8 | 256 => ColorMode::Color256,
             24 | 32 => ColorMode::TrueColor,
             _ => {
-                eprintln!("invalid --colormode: {} (allowed: 0,16,8/256,24/32)", m);
+                eprintln!("invalid {}: {} (allowed: 0,16,8/256,24/32)", name, m);
                 std::process::exit(1);
             }
         };
@@ -264,7 +280,7 @@ fn color_mode_label(m: ColorMode) -> &'static str {
     }
 }
 
-fn print_doctor_report(args: &Args) {
+fn print_doctor_report(args: &Args, config: Option<&ConfigFile>, theme_file: Option<&ThemeFile>) {
     let lang = env::var("LANG").unwrap_or_default();
     let lc_all = env::var("LC_ALL").unwrap_or_default();
     let lc_ctype = env::var("LC_CTYPE").unwrap_or_default();
@@ -278,7 +294,7 @@ fn print_doctor_report(args: &Args) {
     let locale_utf8 = locale_blob.to_ascii_uppercase().contains("UTF");
 
     let auto = detect_color_mode_auto();
-    let effective = detect_color_mode(args);
+    let effective = detect_color_mode(args, "--colormode");
 
     println!("DOCTOR REPORT:");
     println!("  stdin_is_tty: {}", if stdin_tty { "yes" } else { "no" });
@@ -332,6 +348,21 @@ fn print_doctor_report(args: &Args) {
         );
     }
 
+    match (config, config_file::default_config_path()) {
+        (Some(c), _) => println!("  config_file: {} (loaded)", c.path.display()),
+        (None, Some(p)) => println!("  config_file: {} (not found)", p.display()),
+        (None, None) => println!("  config_file: (no config directory)"),
+    }
+    match (theme_file, config_file::default_theme_path()) {
+        (Some(t), _) => println!(
+            "  theme_file: {} ({} themes)",
+            t.path.display(),
+            t.themes.len()
+        ),
+        (None, Some(p)) => println!("  theme_file: {} (not found)", p.display()),
+        (None, None) => println!("  theme_file: (no config directory)"),
+    }
+
     println!("  color_auto_detected: {}", color_mode_label(auto));
     if args.colormode.is_some() {
         println!("  color_forced: {}", color_mode_label(effective));
@@ -360,7 +391,7 @@ fn print_doctor_report(args: &Args) {
         println!("  chars_override: {}", spec);
     }
 
-    let cs = match charset_from_str(&charset_preset, def_ascii) {
+    let cs = match charset_expr_groups(&charset_preset, def_ascii) {
         Ok(v) => v,
         Err(e) => {
             println!("  charset_parse_error: {}", e);
@@ -370,6 +401,7 @@ fn print_doctor_report(args: &Args) {
 
     let uses_katakana = cs.contains(Charset::KATAKANA);
     let uses_unicode = uses_katakana
+        || charset_preset.contains("script:")
         || cs.contains(Charset::GREEK)
         || cs.contains(Charset::CYRILLIC)
         || cs.contains(Charset::HEBREW)
@@ -462,187 +494,58 @@ fn print_doctor_report(args: &Args) {
     }
 }
 
-fn all_color_schemes() -> &'static [ColorScheme] {
-    &[
-        ColorScheme::Green,
-        ColorScheme::Green2,
-        ColorScheme::Green3,
-        ColorScheme::Yellow,
-        ColorScheme::Orange,
-        ColorScheme::Red,
-        ColorScheme::Blue,
-        ColorScheme::Cyan,
-        ColorScheme::Gold,
-        ColorScheme::Rainbow,
-        ColorScheme::Purple,
-        ColorScheme::Neon,
-        ColorScheme::Fire,
-        ColorScheme::Ocean,
-        ColorScheme::Forest,
-        ColorScheme::Vaporwave,
-        ColorScheme::Gray,
-        ColorScheme::Snow,
-        ColorScheme::Aurora,
-        ColorScheme::FancyDiamond,
-        ColorScheme::Cosmos,
-        ColorScheme::Nebula,
-        ColorScheme::Spectrum20,
-        ColorScheme::Stars,
-        ColorScheme::Mars,
-        ColorScheme::Venus,
-        ColorScheme::Mercury,
-        ColorScheme::Jupiter,
-        ColorScheme::Saturn,
-        ColorScheme::Uranus,
-        ColorScheme::Neptune,
-        ColorScheme::Pluto,
-        ColorScheme::Moon,
-        ColorScheme::Sun,
-        ColorScheme::Comet,
-        ColorScheme::Galaxy,
-        ColorScheme::Supernova,
-        ColorScheme::BlackHole,
-        ColorScheme::Andromeda,
-        ColorScheme::Stardust,
-        ColorScheme::Meteor,
-        ColorScheme::Eclipse,
-        ColorScheme::DeepSpace,
-    ]
-}
-
-fn cycle_color_scheme(current: ColorScheme, dir: i32) -> ColorScheme {
-    let list = all_color_schemes();
-    let Some(pos) = list.iter().position(|&c| c == current) else {
-        return ColorScheme::Green;
-    };
-
-    let n = list.len() as i32;
-    let mut idx = pos as i32 + dir;
-    idx = ((idx % n) + n) % n;
-    list[idx as usize]
-}
-
-fn all_charset_presets() -> &'static [&'static str] {
-    &[
-        "auto",
-        "matrix",
-        "ascii",
-        "extended",
-        "english",
-        "digits",
-        "punc",
-        "binary",
-        "hex",
-        "katakana",
-        "greek",
-        "cyrillic",
-        "hebrew",
-        "blocks",
-        "symbols",
-        "arrows",
-        "retro",
-        "cyberpunk",
-        "hacker",
-        "minimal",
-        "code",
-        "dna",
-        "braille",
-        "runic",
-    ]
-}
+/// Restores the terminal before exiting on SIGINT/SIGTERM/SIGHUP (Ctrl-C on
+/// Windows).
+fn install_exit_handlers() {
+    #[cfg(unix)]
+    {
+        if let Ok(mut signals) = Signals::new([SIGINT, SIGTERM, SIGHUP]) {
+            thread::spawn(move || {
+                if let Some(sig) = signals.forever().next() {
+                    restore_terminal_best_effort();
+                    std::process::exit(128 + sig);
+                }
+            });
+        }
+    }
 
-fn normalize_charset_preset_name(s: &str) -> String {
-    match s.trim().to_ascii_lowercase().as_str() {
-        "bin" | "01" => "binary".to_string(),
-        "dec" | "decimal" => "digits".to_string(),
-        "hexadecimal" => "hex".to_string(),
-        other => other.to_string(),
+    #[cfg(windows)]
+    {
+        if let Err(e) = ctrlc::set_handler(|| {
+            restore_terminal_best_effort();
+            std::process::exit(130);
+        }) {
+            eprintln!("failed to install Ctrl-C handler: {}", e);
+        }
     }
 }
 
-fn cycle_charset_preset(current: &str, dir: i32) -> &'static str {
-    let list = all_charset_presets();
-    let Some(pos) = list.iter().position(|&c| c == current) else {
-        return "binary";
+fn play_main(args: PlayArgs) -> std::io::Result<()> {
+    let cast = std::fs::read_to_string(&args.file)
+        .map_err(|e| e.to_string())
+        .and_then(|text| parse_cast(&text));
+    let cast = match cast {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}: {}", args.file.display(), e);
+            std::process::exit(1);
+        }
     };
 
-    let n = list.len() as i32;
-    let mut idx = pos as i32 + dir;
-    idx = ((idx % n) + n) % n;
-    list[idx as usize]
-}
-
-fn auto_density_factor(cols: u16, lines: u16, fullwidth: bool) -> f32 {
-    let eff_cols = if fullwidth {
-        (cols / 2).max(1)
-    } else {
-        cols.max(1)
-    } as f32;
-    let eff_lines = lines.max(1) as f32;
-
-    let area = eff_cols * eff_lines;
-    let base = 80.0 * 25.0;
-    let factor = (area / base).sqrt();
-    factor.clamp(0.5, 2.0)
-}
-
-fn effective_density(base: f32, cols: u16, lines: u16, fullwidth: bool, auto: bool) -> f32 {
-    let base = base.clamp(0.01, 5.0);
-    if !auto {
-        return base;
-    }
-    (base * auto_density_factor(cols, lines, fullwidth)).clamp(0.01, 5.0)
-}
+    #[cfg(target_os = "linux")]
+    spawn_kill9_terminal_guard();
+    install_exit_handlers();
 
-fn parse_color_scheme(s: &str) -> Result<ColorScheme, String> {
-    match s.trim().to_ascii_lowercase().as_str() {
-        "green" => Ok(ColorScheme::Green),
-        "green2" => Ok(ColorScheme::Green2),
-        "green3" => Ok(ColorScheme::Green3),
-        "yellow" => Ok(ColorScheme::Yellow),
-        "orange" => Ok(ColorScheme::Orange),
-        "red" => Ok(ColorScheme::Red),
-        "blue" => Ok(ColorScheme::Blue),
-        "cyan" => Ok(ColorScheme::Cyan),
-        "gold" => Ok(ColorScheme::Gold),
-        "rainbow" => Ok(ColorScheme::Rainbow),
-        "purple" => Ok(ColorScheme::Purple),
-        "neon" | "synthwave" => Ok(ColorScheme::Neon),
-        "fire" | "inferno" => Ok(ColorScheme::Fire),
-        "ocean" | "deep-sea" | "deep_sea" | "deepsea" => Ok(ColorScheme::Ocean),
-        "forest" | "jungle" => Ok(ColorScheme::Forest),
-        "vaporwave" => Ok(ColorScheme::Vaporwave),
-        "gray" | "grey" => Ok(ColorScheme::Gray),
-        "snow" => Ok(ColorScheme::Snow),
-        "aurora" => Ok(ColorScheme::Aurora),
-        "fancy-diamond" | "fancy_diamond" | "fancydiamond" => Ok(ColorScheme::FancyDiamond),
-        "cosmos" => Ok(ColorScheme::Cosmos),
-        "nebula" => Ok(ColorScheme::Nebula),
-        "spectrum20" | "spectrum-20" | "spectrum_20" | "theme20" | "theme-20" | "theme_20" => {
-            Ok(ColorScheme::Spectrum20)
-        }
-        "stars" | "star" => Ok(ColorScheme::Stars),
-        "mars" => Ok(ColorScheme::Mars),
-        "venus" => Ok(ColorScheme::Venus),
-        "mercury" => Ok(ColorScheme::Mercury),
-        "jupiter" => Ok(ColorScheme::Jupiter),
-        "saturn" => Ok(ColorScheme::Saturn),
-        "uranus" => Ok(ColorScheme::Uranus),
-        "neptune" => Ok(ColorScheme::Neptune),
-        "pluto" => Ok(ColorScheme::Pluto),
-        "moon" => Ok(ColorScheme::Moon),
-        "sun" => Ok(ColorScheme::Sun),
-        "comet" => Ok(ColorScheme::Comet),
-        "galaxy" => Ok(ColorScheme::Galaxy),
-        "supernova" | "super-nova" | "super_nova" => Ok(ColorScheme::Supernova),
-        "blackhole" | "black-hole" | "black_hole" => Ok(ColorScheme::BlackHole),
-        "andromeda" => Ok(ColorScheme::Andromeda),
-        "stardust" | "star-dust" | "star_dust" => Ok(ColorScheme::Stardust),
-        "meteor" => Ok(ColorScheme::Meteor),
-        "eclipse" => Ok(ColorScheme::Eclipse),
-        "deepspace" | "deep-space" | "deep_space" => Ok(ColorScheme::DeepSpace),
-        _ => Err(format!("invalid color: {} (see --list-colors)", s)),
-    }
+    let mut term = Terminal::new()?;
+    play(
+        &mut term,
+        &cast,
+        PlayOptions {
+            speed: args.speed,
+            looping: args.looping,
+            hold: true,
+        },
+    )
 }
 
 fn main() -> std::io::Result<()> {
@@ -651,6 +554,8 @@ fn main() -> std::io::Result<()> {
         eprintln!("{}", info);
     }));
 
+    let mut argv: Vec<std::ffi::OsString> = env::args_os().collect();
+
     let mut cmd = Args::command();
     cmd = cmd.styles(clap_styles());
     cmd = cmd.before_help(default_params_usage_for_help());
@@ -667,23 +572,79 @@ fn main() -> std::io::Result<()> {
     }
     cmd.build();
 
-    let mut argv: Vec<std::ffi::OsString> = env::args_os().collect();
     for arg in argv.iter_mut().skip(1) {
         if arg == "-mB" || arg == "-mb" {
             *arg = "--message-no-border".into();
         }
     }
 
-    let matches = cmd.get_matches_from(argv);
+    let matches = cmd.clone().get_matches_from(argv.clone());
+    let cli_args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    if let Some(Command::Play(play_args)) = cli_args.command {
+        return play_main(play_args);
+    }
+
+    let config = match ConfigFile::load(cli_args.config.as_deref()) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if cli_args.list_profiles {
+        let profiles = config.as_ref().map(|c| c.profiles()).unwrap_or_default();
+        print_list_profiles(config.as_ref().map(|c| c.path.as_path()), &profiles);
+        return Ok(());
+    }
+
+    let profile = match (
+        &cli_args.profile,
+        env::var(config_file::env_var_name("profile")),
+    ) {
+        (Some(p), _) => Some(p.clone()),
+        (None, Ok(p)) if !p.trim().is_empty() => Some(p),
+        _ => match config.as_ref().map(|c| c.default_profile()).transpose() {
+            Ok(p) => p.flatten().map(str::to_string),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let layered = match layer_args(&cmd, &matches, &argv, config.as_ref(), profile.as_deref()) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let matches = cmd.get_matches_from(layered.argv.clone());
     let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let opt = |long: &str| layered.label(long);
+
+    let theme_file = match config_file::load_themes(args.theme_file.as_deref()) {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let custom_themes = theme_file.as_ref().map_or(&[][..], |t| &t.themes[..]);
 
     if args.list_charsets {
         print_list_charsets();
         return Ok(());
     }
 
+    if args.list_scripts {
+        print_list_scripts();
+        return Ok(());
+    }
+
     if args.list_colors {
-        print_list_colors();
+        print_list_colors(theme_file.as_ref());
         return Ok(());
     }
 
@@ -693,7 +654,7 @@ fn main() -> std::io::Result<()> {
     }
 
     if args.doctor {
-        print_doctor_report(&args);
+        print_doctor_report(&args, config.as_ref(), theme_file.as_ref());
         return Ok(());
     }
 
@@ -701,7 +662,7 @@ fn main() -> std::io::Result<()> {
         let colorterm = env::var("COLORTERM").unwrap_or_default();
         let term = env::var("TERM").unwrap_or_default();
         let auto = detect_color_mode_auto();
-        let effective = detect_color_mode(&args);
+        let effective = detect_color_mode(&args, &opt("colormode"));
 
         println!("BITCOLOR CHECK:");
         println!(
@@ -743,89 +704,229 @@ fn main() -> std::io::Result<()> {
     }
 
     let def_ascii = default_to_ascii();
-    let color_mode = detect_color_mode(&args);
+    let color_mode = detect_color_mode(&args, &opt("colormode"));
 
-    let shading_mode = match require_u8_range("--shadingmode", args.shading_mode, 0, 1) {
-        1 => ShadingMode::DistanceFromHead,
-        _ => ShadingMode::Random,
-    };
+    let shading_mode = SHADING_MODES[require_u8_range(
+        &opt("shadingmode"),
+        args.shading_mode,
+        0,
+        SHADING_MODES.len() as u8 - 1,
+    ) as usize];
 
-    let bold_mode = match require_u8_range("--bold", args.bold, 0, 2) {
+    let bold_mode = match require_u8_range(&opt("bold"), args.bold, 0, 2) {
         0 => BoldMode::Off,
         2 => BoldMode::All,
         _ => BoldMode::Random,
     };
 
-    let target_fps = require_f64_range("--fps", args.fps, 1.0, 240.0);
+    let target_fps = require_f64_range(&opt("fps"), args.fps, 1.0, 240.0);
     let duration_s = args.duration.map(|s| {
         if !s.is_finite() {
-            eprintln!("failed to apply --duration {} (must be a finite number)", s);
+            eprintln!(
+                "failed to apply {} {} (must be a finite number)",
+                opt("duration"),
+                s
+            );
             std::process::exit(1);
         }
         if s > 0.0 {
-            return require_f64_range("--duration", s, 0.1, 86400.0);
+            return require_f64_range(&opt("duration"), s, 0.1, 86400.0);
         }
         s
     });
 
-    let color_scheme = match parse_color_scheme(&args.color) {
+    let mut color_scheme = match parse_color_scheme_with(&args.color, custom_themes) {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("{}", e);
+            eprintln!("{}{}", layered.context("color"), e);
             std::process::exit(1);
         }
     };
 
-    let glitch_pct = require_f32_range("--glitchpct", args.glitch_pct, 0.0, 100.0);
-    let glitch_low = require_u16_range("--glitchms low", args.glitch_ms.low, 1, 5000);
-    let glitch_high = require_u16_range("--glitchms high", args.glitch_ms.high, 1, 5000);
-    let linger_low = require_u16_range("--lingerms low", args.linger_ms.low, 1, 60000);
-    let linger_high = require_u16_range("--lingerms high", args.linger_ms.high, 1, 60000);
-    let short_pct = require_f32_range("--shortpct", args.shortpct, 0.0, 100.0);
-    let die_early_pct = require_f32_range("--rippct", args.rippct, 0.0, 100.0);
-    let max_dpc = require_u8_range("--maxdpc", args.max_droplets_per_column, 1, 3);
-    let speed = require_f32_range("--speed", args.speed, 0.001, 1000.0);
+    let crossfade = require_f64_range(&opt("crossfade"), args.crossfade.as_secs_f64(), 0.0, 60.0);
+    let color_schemes: Vec<ColorScheme> = all_color_schemes()
+        .iter()
+        .cloned()
+        .chain(custom_themes.iter().cloned().map(ColorScheme::Custom))
+        .collect();
+    let color_cycle = args.cycle_colors.as_ref().map(|cycle| {
+        let themes = if cycle.themes.is_empty() {
+            color_schemes.clone()
+        } else {
+            let listed: Vec<_> = cycle
+                .themes
+                .iter()
+                .map(|name| match parse_color_scheme_with(name, custom_themes) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("{}{}", layered.context("cycle-colors"), e);
+                        std::process::exit(1);
+                    }
+                })
+                .collect();
+            // With an explicit list, start on its first theme unless -c says otherwise.
+            if matches.value_source("color") == Some(ValueSource::DefaultValue) {
+                color_scheme = listed[0].clone();
+            }
+            listed
+        };
+        ColorCycle {
+            interval: cycle.interval,
+            themes,
+            crossfade: Duration::from_secs_f64(crossfade),
+        }
+    });
+
+    let color_adjust = ColorAdjust {
+        brightness: require_f32_range(&opt("brightness"), args.brightness, 0.1, 3.0),
+        contrast: require_f32_range(&opt("contrast"), args.contrast, 0.1, 3.0),
+        saturation: require_f32_range(&opt("saturation"), args.saturation, 0.0, 3.0),
+        hue_shift: require_f32_range(&opt("hue-shift"), args.hue_shift, -180.0, 180.0),
+        simulate_cvd: args.simulate_cvd.map(|c| match c {
+            CvdArg::Protan => Cvd::Protan,
+            CvdArg::Deutan => Cvd::Deutan,
+            CvdArg::Tritan => Cvd::Tritan,
+        }),
+    };
+
+    let glitch_pct = require_f32_range(&opt("glitchpct"), args.glitch_pct, 0.0, 100.0);
+    let glitch_low = require_u16_range(
+        &format!("{} low", opt("glitchms")),
+        args.glitch_ms.low,
+        1,
+        5000,
+    );
+    let glitch_high = require_u16_range(
+        &format!("{} high", opt("glitchms")),
+        args.glitch_ms.high,
+        1,
+        5000,
+    );
+    let linger_low = require_u16_range(
+        &format!("{} low", opt("lingerms")),
+        args.linger_ms.low,
+        1,
+        60000,
+    );
+    let linger_high = require_u16_range(
+        &format!("{} high", opt("lingerms")),
+        args.linger_ms.high,
+        1,
+        60000,
+    );
+    let short_pct = require_f32_range(&opt("shortpct"), args.shortpct, 0.0, 100.0);
+    let die_early_pct = require_f32_range(&opt("rippct"), args.rippct, 0.0, 100.0);
+    let max_dpc = require_u8_range(&opt("maxdpc"), args.max_droplets_per_column, 1, 3);
+    let speed = require_f32_range(&opt("speed"), args.speed, 0.001, 1000.0);
 
     let mut user_ranges: Vec<(char, char)> = Vec::new();
     if let Some(spec) = &args.chars {
-        match parse_user_hex_chars(spec) {
-            Ok(list) => {
-                if list.len() % 2 != 0 {
-                    eprintln!("--chars: odd number of unicode chars given (must be even)");
-                    std::process::exit(1);
-                }
-                for pair in list.chunks(2) {
-                    let a = pair[0];
-                    let b = pair[1];
-                    user_ranges.push((a, b));
-                }
+        match parse_char_ranges(spec) {
+            Ok(ranges) => user_ranges.extend(ranges),
+            Err(e) => {
+                eprintln!("{}{}", layered.context("chars"), e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(text) = &args.chars_literal {
+        match parse_literal_chars(text) {
+            Ok(list) => user_ranges.extend(list.into_iter().map(|c| (c, c))),
+            Err(e) => {
+                eprintln!("{}{}", layered.context("chars-literal"), e);
+                std::process::exit(1);
             }
+        }
+    }
+    if let Some(path) = &args.chars_file {
+        let list = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_literal_chars(&text));
+        match list {
+            Ok(list) => user_ranges.extend(list.into_iter().map(|c| (c, c))),
             Err(e) => {
-                eprintln!("{}", e);
+                eprintln!("{}: {}", path.display(), e);
                 std::process::exit(1);
             }
         }
     }
 
-    let charset = match charset_from_str(&args.charset, def_ascii) {
+    let chars = weighted_chars_from_str(&args.charset, &user_ranges, def_ascii);
+    let chars = match chars {
         Ok(c) => c,
         Err(e) => {
-            eprintln!("{}", e);
+            eprintln!("{}{}", layered.context("charset"), e);
             std::process::exit(1);
         }
     };
 
-    let mut charset_preset = normalize_charset_preset_name(&args.charset);
+    let charset_preset = normalize_charset_preset_name(&args.charset);
+    // Wide glyphs overlap their neighbours unless droplets skip every other column.
+    let fullwidth = args.fullwidth || chars.iter().any(|&(c, _)| is_wide(c));
 
-    let chars = build_chars(charset, &user_ranges, def_ascii);
+    let seed = args.seed.unwrap_or_else(rand::random);
 
     let density_auto = matches.value_source("density") == Some(ValueSource::DefaultValue);
-    let base_density = require_f32_range("--density", args.density, 0.01, 5.0);
+    let reduced_motion =
+        if matches.value_source("reduced_motion") == Some(ValueSource::DefaultValue) {
+            reduced_motion_from_env()
+        } else {
+            args.reduced_motion
+        };
+    let base_density = require_f32_range(&opt("density"), args.density, 0.01, 5.0);
+
+    let new_cloud = |w: u16, h: u16, now: Instant| {
+        let density = effective_density(base_density, w, h, fullwidth, density_auto);
+
+        let mut cloud = Cloud::builder()
+            .color_mode(color_mode)
+            .full_width(fullwidth)
+            .shading_mode(shading_mode)
+            .bold_mode(bold_mode)
+            .async_mode(args.async_mode)
+            .default_background(matches!(
+                args.color_bg,
+                ColorBg::DefaultBackground | ColorBg::Transparent
+            ))
+            .color_scheme(color_scheme)
+            .seed(seed)
+            .glitchy(!args.noglitch)
+            .build();
+
+        cloud.set_glitch_pct(glitch_pct / 100.0);
+        cloud.set_glitch_times(glitch_low, glitch_high);
+        cloud.set_linger_times(linger_low, linger_high);
+        cloud.set_short_pct(short_pct / 100.0);
+        cloud.set_die_early_pct(die_early_pct / 100.0);
+        cloud.set_max_droplets_per_column(max_dpc);
+        cloud.set_droplet_density(density);
+        cloud.set_chars_per_sec(speed);
+        cloud.set_reduced_motion(reduced_motion);
+        cloud.set_dither(args.dither);
+        cloud.set_smooth_shading(match args.smooth_shading {
+            SmoothShadingArg::Off => SmoothShading::Off,
+            SmoothShadingArg::Length => SmoothShading::Length,
+            SmoothShadingArg::Time => SmoothShading::Time,
+        });
+        if !color_adjust.is_neutral() {
+            cloud.set_color_adjust(color_adjust);
+        }
+
+        cloud.init_weighted_chars(chars.clone());
+        cloud.reset_at(w, h, now);
+
+        if let Some(msg) = &args.message {
+            cloud.set_message_border(!args.message_no_border);
+            cloud.set_message(msg);
+        }
+        cloud
+    };
 
     if let Some(bench_frames) = args.bench_frames {
         if bench_frames == 0 {
             eprintln!(
-                "failed to apply --bench-frames {} (must be > 0)",
+                "failed to apply {} {} (must be > 0)",
+                opt("bench-frames"),
                 bench_frames
             );
             std::process::exit(1);
@@ -842,46 +943,15 @@ fn main() -> std::io::Result<()> {
                 .unwrap_or(40),
         );
 
-        let density = effective_density(base_density, w, h, args.fullwidth, density_auto);
-
-        let mut cloud = Cloud::new(
-            color_mode,
-            args.fullwidth,
-            shading_mode,
-            bold_mode,
-            args.async_mode,
-            matches!(
-                args.color_bg,
-                ColorBg::DefaultBackground | ColorBg::Transparent
-            ),
-            color_scheme,
-        );
-
-        cloud.glitchy = !args.noglitch;
-        cloud.set_glitch_pct(glitch_pct / 100.0);
-        cloud.set_glitch_times(glitch_low, glitch_high);
-        cloud.set_linger_times(linger_low, linger_high);
-        cloud.short_pct = short_pct / 100.0;
-        cloud.die_early_pct = die_early_pct / 100.0;
-        cloud.set_max_droplets_per_column(max_dpc);
-        cloud.set_droplet_density(density);
-        cloud.set_chars_per_sec(speed);
-
-        cloud.init_chars(chars);
-        cloud.reset(w, h);
-
-        if let Some(msg) = &args.message {
-            cloud.set_message_border(!args.message_no_border);
-            cloud.set_message(msg);
-        }
+        let mut sim_now = Instant::now();
+        let mut cloud = new_cloud(w, h, sim_now);
 
-        let mut frame = Frame::new(w, h, cloud.palette.bg);
+        let mut frame = Frame::new(w, h, cloud.palette().bg);
 
         let target_period = Duration::from_secs_f64(1.0 / target_fps);
         cloud.set_max_sim_delta(target_period);
 
         let warmup_frames = (bench_frames / 10).clamp(10, 200);
-        let mut sim_now = Instant::now();
 
         for _ in 0..warmup_frames {
             sim_now += target_period;
@@ -902,28 +972,145 @@ fn main() -> std::io::Result<()> {
         println!("  cols: {}", w);
         println!("  lines: {}", h);
         println!("  frames: {}", bench_frames);
+        println!("  seed: {}", cloud.seed());
         println!("  elapsed_s: {:.6}", elapsed_s);
         println!("  frames_per_s: {:.3}", fps);
         return Ok(());
     }
 
+    if args.export_frames.is_some() || args.export_gif.is_some() {
+        let frames = require_u32_range(&opt("frames"), args.frames, 1, 100_000);
+        let w = require_u16_range(&opt("size"), args.size.width, 1, 1000);
+        let h = require_u16_range(&opt("size"), args.size.height, 1, 1000);
+        let cell_w = require_u16_range(&opt("cell-size"), args.cell_size.width, 1, 64) as u32;
+        let cell_h = require_u16_range(&opt("cell-size"), args.cell_size.height, 1, 64) as u32;
+        if args.export_frames.is_some() {
+            if let Err(e) = image_size(w, h, cell_w, cell_h) {
+                eprintln!(
+                    "failed to apply {} {} with {} {} ({})",
+                    opt("size"),
+                    args.size,
+                    opt("cell-size"),
+                    args.cell_size,
+                    e
+                );
+                std::process::exit(1);
+            }
+        }
+        let ext = match args.export_format {
+            ExportFormat::Png => "png",
+            ExportFormat::Ppm => "ppm",
+        };
+        if let Some(dir) = &args.export_frames {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                eprintln!(
+                    "failed to apply {} {} ({})",
+                    opt("export-frames"),
+                    dir.display(),
+                    e
+                );
+                std::process::exit(1);
+            }
+        }
+
+        let mut sim_now = Instant::now();
+        let mut cloud = new_cloud(w, h, sim_now);
+        let mut frame = Frame::new(w, h, cloud.palette().bg);
+        let period = Duration::from_secs_f64(1.0 / target_fps);
+        cloud.set_max_sim_delta(period);
+
+        let mut gif = match &args.export_gif {
+            None => None,
+            Some(path) => {
+                let gif = File::create(path).and_then(|f| {
+                    GifExporter::new(
+                        BufWriter::new(f),
+                        w,
+                        h,
+                        cell_w,
+                        cell_h,
+                        &cloud.theme_palette(),
+                    )
+                });
+                match gif {
+                    Ok(g) => Some(g),
+                    Err(e) => {
+                        eprintln!(
+                            "failed to apply {} {} ({})",
+                            opt("export-gif"),
+                            path.display(),
+                            e
+                        );
+                        std::process::exit(1);
+                    }
+                }
+            }
+        };
+
+        for i in 1..=frames {
+            sim_now += period;
+            cloud.rain_at(&mut frame, sim_now);
+            if let Some(gif) = gif.as_mut() {
+                gif.push(&frame, period)?;
+            }
+            frame.clear_dirty();
+
+            if let Some(dir) = &args.export_frames {
+                let img = rasterize(&frame, cell_w, cell_h);
+                let path = dir.join(format!("frame-{:05}.{}", i, ext));
+                let out = BufWriter::new(File::create(&path)?);
+                match args.export_format {
+                    ExportFormat::Png => img.write_png(out)?,
+                    ExportFormat::Ppm => img.write_ppm(out)?,
+                }
+            }
+        }
+        if let Some(gif) = gif {
+            gif.finish()?.flush()?;
+        }
+
+        println!("EXPORT:");
+        if let Some(dir) = &args.export_frames {
+            println!("  dir: {}", dir.display());
+        }
+        if let Some(path) = &args.export_gif {
+            println!("  gif: {}", path.display());
+        }
+        println!("  frames: {}", frames);
+        println!(
+            "  size: {}x{} cells, {}x{} px",
+            w,
+            h,
+            w as u32 * cell_w,
+            h as u32 * cell_h
+        );
+        println!("  fps: {:.3}", target_fps);
+        println!("  seed: {}", cloud.seed());
+        return Ok(());
+    }
+
+    let record_file = args.record.as_ref().map(|path| {
+        File::create(path).unwrap_or_else(|e| {
+            eprintln!(
+                "failed to apply {} {} ({})",
+                opt("record"),
+                path.display(),
+                e
+            );
+            std::process::exit(1);
+        })
+    });
+
     #[cfg(target_os = "linux")]
     spawn_kill9_terminal_guard();
 
     #[cfg(unix)]
     let term_reinit = Arc::new(AtomicBool::new(false));
 
+    install_exit_handlers();
+
     #[cfg(unix)]
     {
-        if let Ok(mut signals) = Signals::new([SIGINT, SIGTERM, SIGHUP]) {
-            thread::spawn(move || {
-                if let Some(sig) = signals.forever().next() {
-                    restore_terminal_best_effort();
-                    std::process::exit(128 + sig);
-                }
-            });
-        }
-
         let term_reinit = term_reinit.clone();
         if let Ok(mut signals) = Signals::new([SIGTSTP, SIGCONT]) {
             thread::spawn(move || {
@@ -944,53 +1131,14 @@ fn main() -> std::io::Result<()> {
         }
     }
 
-    #[cfg(windows)]
-    {
-        if let Err(e) = ctrlc::set_handler(|| {
-            restore_terminal_best_effort();
-            std::process::exit(130);
-        }) {
-            eprintln!("failed to install Ctrl-C handler: {}", e);
-        }
-    }
-
     let mut term = Terminal::new()?;
     let (w, h) = term.size()?;
-
-    let density = effective_density(base_density, w, h, args.fullwidth, density_auto);
-
-    let mut cloud = Cloud::new(
-        color_mode,
-        args.fullwidth,
-        shading_mode,
-        bold_mode,
-        args.async_mode,
-        matches!(
-            args.color_bg,
-            ColorBg::DefaultBackground | ColorBg::Transparent
-        ),
-        color_scheme,
-    );
-
-    cloud.glitchy = !args.noglitch;
-    cloud.set_glitch_pct(glitch_pct / 100.0);
-    cloud.set_glitch_times(glitch_low, glitch_high);
-    cloud.set_linger_times(linger_low, linger_high);
-    cloud.short_pct = short_pct / 100.0;
-    cloud.die_early_pct = die_early_pct / 100.0;
-    cloud.set_max_droplets_per_column(max_dpc);
-    cloud.set_droplet_density(density);
-    cloud.set_chars_per_sec(speed);
-
-    cloud.init_chars(chars);
-    cloud.reset(w, h);
-
-    if let Some(msg) = &args.message {
-        cloud.set_message_border(!args.message_no_border);
-        cloud.set_message(msg);
+    if let Some(file) = record_file {
+        let out: Box<dyn Write> = Box::new(BufWriter::new(file));
+        term.record(CastWriter::new(out, w, h)?);
     }
 
-    let mut frame = Frame::new(w, h, cloud.palette.bg);
+    let mut cloud = new_cloud(w, h, Instant::now());
 
     let start_time = Instant::now();
     let end_time = args.duration.and_then(|s| {
@@ -1001,291 +1149,56 @@ fn main() -> std::io::Result<()> {
         Some(start_time + Duration::from_secs_f64(s))
     });
 
-    let target_period = Duration::from_secs_f64(1.0 / target_fps);
-    let pause_period = Duration::from_millis(250);
-    let mut next_frame = Instant::now();
-    let mut perf_pressure: f32 = 0.0;
-
-    let mut perf_frames: u64 = 0;
-    let mut perf_drawn_frames: u64 = 0;
-    let mut perf_work_sum_s: f64 = 0.0;
-    let mut perf_work_max_s: f32 = 0.0;
-    let mut perf_pressure_sum: f64 = 0.0;
-    let mut perf_pressure_max: f32 = 0.0;
-    let mut perf_overshoot_frames: u64 = 0;
-
-    while cloud.raining {
-        let frame_period = if cloud.pause {
-            pause_period
-        } else {
-            target_period
-        };
-        let frame_period_s = frame_period.as_secs_f32().max(0.000_001);
-
-        if end_time.is_some_and(|end| Instant::now() >= end) {
-            cloud.raining = false;
-            break;
-        }
-        let mut pending_resize: Option<(u16, u16)> = None;
-
+    let mut opts = RunOptions {
+        target_period: Duration::from_secs_f64(1.0 / target_fps),
+        end_time,
+        screensaver: args.screensaver,
+        fullwidth: args.fullwidth,
+        base_density,
+        density_auto,
+        charset: charset_preset,
+        user_ranges,
+        def_ascii,
+        color_schemes,
+        color_cycle,
         #[cfg(unix)]
-        if term_reinit.swap(false, Ordering::SeqCst) {
-            drop(term);
-            term = Terminal::new()?;
-            let (nw, nh) = term.size()?;
-            pending_resize = Some((nw, nh));
-            cloud.force_draw_everything();
-            next_frame = Instant::now();
-        }
-
-        loop {
-            while Terminal::poll_event(Duration::from_millis(0))? {
-                let ev = Terminal::read_event()?;
-                match ev {
-                    Event::Resize(nw, nh) => {
-                        pending_resize = Some((nw, nh));
-                    }
-                    Event::Key(k) if k.kind == KeyEventKind::Press => {
-                        if args.screensaver {
-                            cloud.raining = false;
-                            break;
-                        }
-
-                        match (k.code, k.modifiers) {
-                            (KeyCode::Esc, _) => cloud.raining = false,
-                            (KeyCode::Char('q'), _) => cloud.raining = false,
-                            (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
-                                #[cfg(unix)]
-                                {
-                                    restore_terminal_best_effort();
-                                    term_reinit.store(true, Ordering::SeqCst);
-                                    let _ = low_level::raise(SIGSTOP);
-                                }
-                            }
-                            (KeyCode::Char(' '), _) => {
-                                cloud.reset(frame.width, frame.height);
-                                cloud.force_draw_everything();
-                            }
-                            (KeyCode::Char('c'), _) => {
-                                let next = cycle_color_scheme(cloud.color_scheme(), 1);
-                                cloud.set_color_scheme(next);
-                            }
-                            (KeyCode::Char('C'), _) => {
-                                let prev = cycle_color_scheme(cloud.color_scheme(), -1);
-                                cloud.set_color_scheme(prev);
-                            }
-                            (KeyCode::Char('s'), _) => {
-                                let next = cycle_charset_preset(&charset_preset, 1);
-                                charset_preset = next.to_string();
-                                if let Ok(cs) = charset_from_str(&charset_preset, def_ascii) {
-                                    let chars = build_chars(cs, &user_ranges, def_ascii);
-                                    cloud.init_chars(chars);
-                                    cloud.force_draw_everything();
-                                }
-                            }
-                            (KeyCode::Char('S'), _) => {
-                                let prev = cycle_charset_preset(&charset_preset, -1);
-                                charset_preset = prev.to_string();
-                                if let Ok(cs) = charset_from_str(&charset_preset, def_ascii) {
-                                    let chars = build_chars(cs, &user_ranges, def_ascii);
-                                    cloud.init_chars(chars);
-                                    cloud.force_draw_everything();
-                                }
-                            }
-                            (KeyCode::Char('a'), _) => {
-                                cloud.set_async(!cloud.async_mode);
-                            }
-                            (KeyCode::Char('g'), _) => {
-                                cloud.set_glitchy(!cloud.glitchy);
-                            }
-                            (KeyCode::Char('p'), _) => {
-                                cloud.toggle_pause();
-                            }
-                            (KeyCode::Up, _) => {
-                                let mut cps = cloud.chars_per_sec;
-                                if cps <= 0.5 {
-                                    cps *= 2.0;
-                                } else {
-                                    cps += 1.0;
-                                }
-                                cloud.set_chars_per_sec(cps.min(1000.0));
-                            }
-                            (KeyCode::Down, _) => {
-                                let mut cps = cloud.chars_per_sec;
-                                if cps <= 1.0 {
-                                    cps /= 2.0;
-                                } else {
-                                    cps -= 1.0;
-                                }
-                                cloud.set_chars_per_sec(cps.max(0.001));
-                            }
-                            (KeyCode::Left, _) => {
-                                if cloud.glitchy {
-                                    let gp = (cloud.glitch_pct - 0.05).max(0.0);
-                                    cloud.set_glitch_pct(gp);
-                                }
-                            }
-                            (KeyCode::Right, _) => {
-                                if cloud.glitchy {
-                                    let gp = (cloud.glitch_pct + 0.05).min(1.0);
-                                    cloud.set_glitch_pct(gp);
-                                }
-                            }
-                            (KeyCode::Tab, _) => {
-                                let sm = if cloud.shading_distance {
-                                    ShadingMode::Random
-                                } else {
-                                    ShadingMode::DistanceFromHead
-                                };
-                                cloud.set_shading_mode(sm);
-                            }
-                            (KeyCode::Char('-'), _)
-                            | (KeyCode::Char('['), _)
-                            | (KeyCode::Char('_'), _) => {
-                                let d = (cloud.droplet_density - 0.25).max(0.01);
-                                cloud.set_droplet_density(d);
-                            }
-                            (KeyCode::Char('+'), _)
-                            | (KeyCode::Char('='), KeyModifiers::SHIFT)
-                            | (KeyCode::Char(']'), _) => {
-                                let d = (cloud.droplet_density + 0.25).min(5.0);
-                                cloud.set_droplet_density(d);
-                            }
-                            (KeyCode::Char('1'), _) => cloud.set_color_scheme(ColorScheme::Green),
-                            (KeyCode::Char('2'), _) => cloud.set_color_scheme(ColorScheme::Green2),
-                            (KeyCode::Char('3'), _) => cloud.set_color_scheme(ColorScheme::Green3),
-                            (KeyCode::Char('4'), _) => cloud.set_color_scheme(ColorScheme::Gold),
-                            (KeyCode::Char('5'), _) => cloud.set_color_scheme(ColorScheme::Neon),
-                            (KeyCode::Char('6'), _) => cloud.set_color_scheme(ColorScheme::Red),
-                            (KeyCode::Char('7'), _) => cloud.set_color_scheme(ColorScheme::Blue),
-                            (KeyCode::Char('8'), _) => cloud.set_color_scheme(ColorScheme::Cyan),
-                            (KeyCode::Char('9'), _) => cloud.set_color_scheme(ColorScheme::Purple),
-                            (KeyCode::Char('0'), _) => cloud.set_color_scheme(ColorScheme::Gray),
-                            (KeyCode::Char('!'), _) => cloud.set_color_scheme(ColorScheme::Rainbow),
-                            (KeyCode::Char('@'), _) => cloud.set_color_scheme(ColorScheme::Yellow),
-                            (KeyCode::Char('#'), _) => cloud.set_color_scheme(ColorScheme::Orange),
-                            (KeyCode::Char('$'), _) => cloud.set_color_scheme(ColorScheme::Fire),
-                            (KeyCode::Char('%'), _) => {
-                                cloud.set_color_scheme(ColorScheme::Vaporwave)
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
-            }
-
-            if !cloud.raining || pending_resize.is_some() {
-                break;
-            }
-
-            let now = Instant::now();
-            if now >= next_frame {
-                break;
-            }
-
-            let mut timeout = next_frame - now;
-            if let Some(end) = end_time {
-                if now >= end {
-                    break;
-                }
-                timeout = timeout.min(end - now);
-            }
-            let _ = Terminal::poll_event(timeout)?;
-        }
-
-        if !cloud.raining {
-            break;
-        }
-
-        if let Some((nw, nh)) = pending_resize {
-            cloud.reset(nw, nh);
-            frame = Frame::new(nw, nh, cloud.palette.bg);
-            if density_auto {
-                cloud.set_droplet_density(effective_density(
-                    base_density,
-                    nw,
-                    nh,
-                    args.fullwidth,
-                    true,
-                ));
-            }
-            cloud.force_draw_everything();
-        }
-
-        cloud.set_perf_pressure(perf_pressure);
-        let sim_base_s = frame_period.as_secs_f64() * 3.0;
-        let sim_factor = (1.0 - (perf_pressure as f64) * 0.7).clamp(0.3, 1.0);
-        let sim_min_s = (frame_period.as_secs_f64() * 0.5).max(0.001);
-        let sim_max_s = sim_base_s.min(0.5);
-        let sim_cap_s = (sim_base_s * sim_factor).clamp(sim_min_s, sim_max_s);
-        cloud.set_max_sim_delta(Duration::from_secs_f64(sim_cap_s));
-
-        let work_start = Instant::now();
-        cloud.rain(&mut frame);
-        let did_draw = frame.is_dirty_all() || !frame.dirty_indices().is_empty();
-        if did_draw {
-            term.draw(&mut frame)?;
-        }
-        let work_s = work_start.elapsed().as_secs_f32();
-        let overshoot = ((work_s / frame_period_s) - 1.0).clamp(0.0, 2.0);
-        if overshoot > 0.0 {
-            perf_pressure = (perf_pressure + (overshoot * 0.25)).min(1.0);
-        } else {
-            perf_pressure = (perf_pressure - 0.02).max(0.0);
-        }
-
-        if args.perf_stats {
-            perf_frames = perf_frames.saturating_add(1);
-            if did_draw {
-                perf_drawn_frames = perf_drawn_frames.saturating_add(1);
-            }
-            perf_work_sum_s += work_s as f64;
-            perf_work_max_s = perf_work_max_s.max(work_s);
-            perf_pressure_sum += perf_pressure as f64;
-            perf_pressure_max = perf_pressure_max.max(perf_pressure);
-            if overshoot > 0.0 {
-                perf_overshoot_frames = perf_overshoot_frames.saturating_add(1);
-            }
-        }
-
-        let now = Instant::now();
-        next_frame = next_frame.checked_add(frame_period).unwrap_or(now);
-        if now > next_frame {
-            next_frame = now.checked_add(frame_period).unwrap_or(now);
-        }
-    }
+        term_reinit: Some(term_reinit),
+        #[cfg(not(unix))]
+        term_reinit: None,
+    };
+    let perf = run_loop(&mut term, &mut cloud, &mut opts)?;
 
     if args.perf_stats {
         drop(term);
         let elapsed = start_time.elapsed();
         let elapsed_s = elapsed.as_secs_f64().max(0.000_001);
 
-        let frames = perf_frames.max(1);
-        let avg_work_ms = (perf_work_sum_s / frames as f64) * 1000.0;
-        let avg_pressure = perf_pressure_sum / frames as f64;
-        let avg_fps = (perf_frames as f64) / elapsed_s;
-        let drawn_ratio = (perf_drawn_frames as f64) / (perf_frames as f64).max(1.0);
+        let frames = perf.frames.max(1);
+        let avg_work_ms = (perf.work_sum_s / frames as f64) * 1000.0;
+        let avg_pressure = perf.pressure_sum / frames as f64;
+        let avg_fps = (perf.frames as f64) / elapsed_s;
+        let drawn_ratio = (perf.drawn_frames as f64) / (perf.frames as f64).max(1.0);
 
         println!("PERF STATS:");
         println!("  elapsed_s: {:.3}", elapsed_s);
+        println!("  seed: {}", cloud.seed());
         println!("  target_fps: {:.3}", target_fps);
         println!("  avg_fps: {:.3}", avg_fps);
-        println!("  frames: {}", perf_frames);
+        println!("  frames: {}", perf.frames);
         println!(
             "  drawn_frames: {} ({:.1}%)",
-            perf_drawn_frames,
+            perf.drawn_frames,
             drawn_ratio * 100.0
         );
         println!("  avg_work_ms: {:.3}", avg_work_ms);
-        println!("  max_work_ms: {:.3}", perf_work_max_s as f64 * 1000.0);
+        println!("  max_work_ms: {:.3}", perf.work_max_s as f64 * 1000.0);
         println!(
             "  overshoot_frames: {} ({:.1}%)",
-            perf_overshoot_frames,
-            (perf_overshoot_frames as f64) / (perf_frames as f64).max(1.0) * 100.0
+            perf.overshoot_frames,
+            (perf.overshoot_frames as f64) / (perf.frames as f64).max(1.0) * 100.0
         );
         println!("  avg_perf_pressure: {:.3}", avg_pressure);
-        println!("  max_perf_pressure: {:.3}", perf_pressure_max);
+        println!("  max_perf_pressure: {:.3}", perf.pressure_max);
     }
 
     Ok(())
@@ -0,0 +1,181 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Luminance rate limiting for reduced motion.
+//!
+//! A [`LuminanceLimiter`] sits between the rain and the screen: the rain is
+//! drawn into the limiter's own [`Frame`], and each call to
+//! [`LuminanceLimiter::apply`] moves the screen toward it by at most
+//! [`CELL_RATE`] per cell and [`FIELD_RATE`] across the whole field. Glyphs
+//! fade in and out instead of popping, and full redraws (shading switches,
+//! resets, theme changes) become fades.
+
+use std::time::Duration;
+
+use crate::cell::Cell;
+use crate::frame::Frame;
+use crate::oklab::Oklab;
+use crate::palette::{color_to_rgb, quantize};
+use crate::runtime::ColorMode;
+
+/// Most OKLab lightness (0-1) a cell changes by per second. A full
+/// dark-light-dark swing takes two thirds of a second, well under the WCAG
+/// limit of three flashes a second.
+pub const CELL_RATE: f32 = 3.0;
+
+/// Most the mean lightness of all cells changes by per second, so the field
+/// as a whole never brightens or darkens abruptly.
+pub const FIELD_RATE: f32 = 0.5;
+
+/// Cells within this much lightness of their target are shown as drawn.
+const SETTLED: f32 = 1e-3;
+
+/// Perceived lightness of a cell: its glyph color, or the background for
+/// blanks and default colors.
+fn lightness(cell: &Cell) -> f32 {
+    let glyph = cell.fg.filter(|_| cell.ch != ' ');
+    glyph
+        .or(cell.bg)
+        .and_then(color_to_rgb)
+        .map_or(0.0, |rgb| Oklab::from_rgb(rgb).l)
+}
+
+/// `cell` with its glyph color moved to lightness `l`, keeping the hue.
+fn with_lightness(cell: Cell, l: f32, mode: ColorMode) -> Cell {
+    let Some(rgb) = cell.fg.and_then(color_to_rgb) else {
+        return cell;
+    };
+    let lab = Oklab::from_rgb(rgb);
+    let moved = Oklab { l, ..lab }.with_chroma(lab.chroma());
+    Cell {
+        fg: Some(quantize(mode, moved.to_rgb())),
+        ..cell
+    }
+}
+
+/// Limits how fast each cell, and the field as a whole, changes lightness.
+#[derive(Clone, Debug)]
+pub struct LuminanceLimiter {
+    target: Frame,
+    shown: Vec<f32>,
+    steps: Vec<f32>,
+}
+
+impl LuminanceLimiter {
+    /// A limiter for `screen`, starting from what it shows now.
+    pub fn new(screen: &Frame) -> Self {
+        let len = screen.width as usize * screen.height as usize;
+        Self {
+            target: screen.clone(),
+            shown: (0..len)
+                .map(|i| lightness(&screen.cell_at_index(i)))
+                .collect(),
+            steps: Vec::with_capacity(len),
+        }
+    }
+
+    /// Whether the limiter was made for a frame of this size.
+    pub fn fits(&self, screen: &Frame) -> bool {
+        (self.target.width, self.target.height) == (screen.width, screen.height)
+    }
+
+    /// The frame the rain should be drawn into.
+    pub fn target(&mut self) -> &mut Frame {
+        &mut self.target
+    }
+
+    /// Moves `screen` toward the target frame, `dt` after the last call.
+    pub fn apply(&mut self, screen: &mut Frame, dt: Duration, mode: ColorMode) {
+        let dt = dt.as_secs_f32();
+        let cell_max = CELL_RATE * dt;
+
+        self.steps.clear();
+        let mut total = 0.0;
+        for (i, &shown) in self.shown.iter().enumerate() {
+            let step =
+                (lightness(&self.target.cell_at_index(i)) - shown).clamp(-cell_max, cell_max);
+            total += step.abs();
+            self.steps.push(step);
+        }
+        let field_max = FIELD_RATE * dt * self.shown.len() as f32;
+        let scale = if total > field_max {
+            field_max / total
+        } else {
+            1.0
+        };
+
+        let width = self.target.width.max(1) as usize;
+        for (i, shown) in self.shown.iter_mut().enumerate() {
+            let target = self.target.cell_at_index(i);
+            *shown += self.steps[i] * scale;
+            let cell = if (lightness(&target) - *shown).abs() <= SETTLED {
+                target
+            } else if target.ch != ' ' {
+                with_lightness(target, *shown, mode)
+            } else {
+                // The glyph is gone from the rain; fade out what is on screen.
+                let old = screen.cell_at_index(i);
+                with_lightness(
+                    Cell {
+                        bg: target.bg,
+                        ..old
+                    },
+                    *shown,
+                    mode,
+                )
+            };
+            screen.set((i % width) as u16, (i / width) as u16, cell);
+        }
+        self.target.clear_dirty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crossterm::style::Color;
+
+    fn glyph(r: u8, g: u8, b: u8) -> Cell {
+        Cell {
+            ch: 'x',
+            fg: Some(Color::Rgb { r, g, b }),
+            bg: None,
+            bold: false,
+        }
+    }
+
+    #[test]
+    fn cells_and_field_change_lightness_gradually() {
+        let mut screen = Frame::new(40, 1, None);
+        let mut limiter = LuminanceLimiter::new(&screen);
+        let dt = Duration::from_millis(50);
+
+        // One cell lighting up is held to the per-cell rate.
+        limiter.target().set(0, 0, glyph(255, 255, 255));
+        limiter.apply(&mut screen, dt, ColorMode::TrueColor);
+        let l = lightness(screen.get(0, 0).unwrap());
+        assert!((l - CELL_RATE * 0.05).abs() < 0.02, "{}", l);
+
+        // Every cell lighting up at once is held to the field rate.
+        let mut screen = Frame::new(4, 1, None);
+        let mut limiter = LuminanceLimiter::new(&screen);
+        for x in 0..4 {
+            limiter.target().set(x, 0, glyph(255, 255, 255));
+        }
+        limiter.apply(&mut screen, dt * 10, ColorMode::TrueColor);
+        let l = lightness(screen.get(3, 0).unwrap());
+        assert!((l - FIELD_RATE * 0.5).abs() < 0.02, "{}", l);
+
+        // Given time, the screen settles on the target and then fades the
+        // glyph out again once the rain clears it.
+        for _ in 0..60 {
+            limiter.apply(&mut screen, dt, ColorMode::TrueColor);
+        }
+        assert_eq!(screen.get(3, 0), Some(&glyph(255, 255, 255)));
+        limiter.target().clear_with_bg(None);
+        limiter.apply(&mut screen, dt, ColorMode::TrueColor);
+        let faded = *screen.get(3, 0).unwrap();
+        assert_eq!(faded.ch, 'x');
+        assert!(lightness(&faded) < 1.0 && lightness(&faded) > 0.9);
+    }
+}
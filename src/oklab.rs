@@ -0,0 +1,172 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! OKLab, a perceptual color space: equal steps in lightness look equal,
+//! and changing lightness keeps the hue.
+
+/// A color in OKLab (`l` 0-1, `a`/`b` roughly -0.4 to 0.4).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Oklab {
+    pub l: f32,
+    pub a: f32,
+    pub b: f32,
+}
+
+pub(crate) fn to_linear(c: u8) -> f32 {
+    let c = c as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+pub(crate) fn from_linear(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl Oklab {
+    pub fn from_rgb((r, g, b): (u8, u8, u8)) -> Self {
+        let (r, g, b) = (to_linear(r), to_linear(g), to_linear(b));
+        let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+        let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+        let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+        Self {
+            l: 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+            a: 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+            b: 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+        }
+    }
+
+    /// Linear sRGB, possibly outside 0-1 when the color is out of gamut.
+    fn to_linear_rgb(self) -> [f32; 3] {
+        let l = (self.l + 0.396_337_78 * self.a + 0.215_803_76 * self.b).powi(3);
+        let m = (self.l - 0.105_561_346 * self.a - 0.063_854_17 * self.b).powi(3);
+        let s = (self.l - 0.089_484_18 * self.a - 1.291_485_5 * self.b).powi(3);
+        [
+            4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+            -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+            -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+        ]
+    }
+
+    /// Whether the color can be shown in sRGB without clipping.
+    pub fn in_gamut(self) -> bool {
+        self.to_linear_rgb()
+            .iter()
+            .all(|&c| (-1e-4..=1.0 + 1e-4).contains(&c))
+    }
+
+    /// Nearest sRGB color, clipping each channel.
+    pub fn to_rgb(self) -> (u8, u8, u8) {
+        let [r, g, b] = self.to_linear_rgb();
+        let c = |v: f32| (from_linear(v.clamp(0.0, 1.0)) * 255.0).round() as u8;
+        (c(r), c(g), c(b))
+    }
+
+    /// Squared perceptual distance.
+    pub fn distance2(self, other: Self) -> f32 {
+        let (dl, da, db) = (self.l - other.l, self.a - other.a, self.b - other.b);
+        dl * dl + da * da + db * db
+    }
+
+    /// Point `t` (0-1) of the way from `self` to `other`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        Self {
+            l: self.l + (other.l - self.l) * t,
+            a: self.a + (other.a - self.a) * t,
+            b: self.b + (other.b - self.b) * t,
+        }
+    }
+
+    /// Chroma (colorfulness).
+    pub fn chroma(self) -> f32 {
+        self.a.hypot(self.b)
+    }
+
+    /// Same lightness and hue with chroma `c`, reduced further if needed to
+    /// stay inside sRGB.
+    pub fn with_chroma(self, c: f32) -> Self {
+        let old = self.chroma();
+        let scale = |k: f32| {
+            let k = if old > 1e-6 { k / old } else { 0.0 };
+            Self {
+                l: self.l,
+                a: self.a * k,
+                b: self.b * k,
+            }
+        };
+        let wanted = scale(c);
+        if wanted.in_gamut() {
+            return wanted;
+        }
+        let (mut lo, mut hi) = (0.0f32, c);
+        for _ in 0..20 {
+            let mid = (lo + hi) / 2.0;
+            if scale(mid).in_gamut() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        scale(lo)
+    }
+}
+
+/// Lightness of the dark tail and the near-white head of [`lightness_ramp`].
+const TAIL_L: f32 = 0.22;
+const HEAD_L: f32 = 0.97;
+
+/// `steps` shades of `base` from a dark tail through `base` itself (in the
+/// middle) to a near-white head, varying OKLab lightness and keeping the hue.
+/// Chroma tapers toward both ends so the tail stays dark and the head white.
+pub fn lightness_ramp(base: (u8, u8, u8), steps: usize) -> Vec<(u8, u8, u8)> {
+    if steps == 0 {
+        return Vec::new();
+    }
+    let base_lab = Oklab::from_rgb(base);
+    let tail_l = TAIL_L.min(base_lab.l * 0.6);
+    let mid_l = base_lab.l.clamp(tail_l + 0.05, HEAD_L - 0.05);
+    let c = base_lab.chroma();
+    let mid = (steps - 1) as f32 / 2.0;
+
+    (0..steps)
+        .map(|i| {
+            let i = i as f32;
+            if steps > 1 && i == mid && mid_l == base_lab.l {
+                return base;
+            }
+            let (l, chroma) = if i <= mid {
+                let t = if mid > 0.0 { i / mid } else { 1.0 };
+                (tail_l + (mid_l - tail_l) * t, c * (0.6 + 0.4 * t))
+            } else {
+                let t = (i - mid) / mid;
+                (mid_l + (HEAD_L - mid_l) * t, c * (1.0 - 0.85 * t))
+            };
+            Oklab { l, ..base_lab }.with_chroma(chroma).to_rgb()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_and_ramps_by_lightness() {
+        for rgb in [(0, 0, 0), (255, 255, 255), (255, 136, 0), (12, 34, 200)] {
+            assert_eq!(Oklab::from_rgb(rgb).to_rgb(), rgb);
+        }
+
+        let base = (0, 120, 200);
+        let ramp = lightness_ramp(base, 9);
+        assert_eq!(ramp.len(), 9);
+        assert_eq!(ramp[4], base);
+        let l: Vec<f32> = ramp.iter().map(|&c| Oklab::from_rgb(c).l).collect();
+        assert!(l.windows(2).all(|w| w[0] < w[1]), "{:?}", l);
+        assert!(l[0] < 0.25 && l[8] > 0.95, "{:?}", l);
+    }
+}
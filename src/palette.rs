@@ -1,9 +1,17 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! Theme palettes.
+
+use std::sync::OnceLock;
+
 use crossterm::style::Color;
 
+use crate::cvd::Cvd;
+use crate::oklab::{lightness_ramp, Oklab};
 use crate::runtime::{ColorMode, ColorScheme};
 
+/// Colors of a theme from dark (tail) to bright (head), plus the background
+/// (`None` = terminal default).
 #[derive(Clone, Debug)]
 pub struct Palette {
     pub colors: Vec<Color>,
@@ -20,81 +28,132 @@ fn from_rgb_list(list: &[(u8, u8, u8)]) -> Vec<Color> {
         .collect()
 }
 
-fn dist2(r0: u8, g0: u8, b0: u8, r1: u8, g1: u8, b1: u8) -> i32 {
-    let dr = (r0 as i32) - (r1 as i32);
-    let dg = (g0 as i32) - (g1 as i32);
-    let db = (b0 as i32) - (b1 as i32);
-    (dr * dr) + (dg * dg) + (db * db)
+/// Xterm's 6x6x6 cube and gray ramp (indices 16-255) in OKLab. Indices 0-15
+/// are left out: terminal themes redefine them.
+fn ansi256_lab() -> &'static [(u8, Oklab)] {
+    static TABLE: OnceLock<Vec<(u8, Oklab)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        (16..=255u8)
+            .filter_map(|i| Some((i, Oklab::from_rgb(color_to_rgb(Color::AnsiValue(i))?))))
+            .collect()
+    })
+}
+
+/// Entry of `table` perceptually closest to `rgb`.
+fn nearest<T: Copy>(table: &[(T, Oklab)], rgb: (u8, u8, u8)) -> T {
+    let lab = Oklab::from_rgb(rgb);
+    table
+        .iter()
+        .min_by(|x, y| lab.distance2(x.1).total_cmp(&lab.distance2(y.1)))
+        .map(|&(c, _)| c)
+        .expect("non-empty table")
 }
 
 fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
-    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    nearest(ansi256_lab(), (r, g, b))
+}
 
-    let r6 = ((r as u16 * 5) + 127) / 255;
-    let g6 = ((g as u16 * 5) + 127) / 255;
-    let b6 = ((b as u16 * 5) + 127) / 255;
-
-    let cr = CUBE_LEVELS[r6 as usize];
-    let cg = CUBE_LEVELS[g6 as usize];
-    let cb = CUBE_LEVELS[b6 as usize];
-    let cube_idx = 16 + (36 * r6 as u8) + (6 * g6 as u8) + (b6 as u8);
-    let cube_dist = dist2(r, g, b, cr, cg, cb);
-
-    let avg = ((r as u16 + g as u16 + b as u16) / 3) as u8;
-    let gray_idx = if avg < 8 {
-        16
-    } else if avg > 238 {
-        231
-    } else {
-        232 + ((avg - 8) / 10)
-    };
-    let (gr, gg, gb) = if gray_idx == 16 {
-        (0, 0, 0)
-    } else if gray_idx == 231 {
-        (255, 255, 255)
-    } else {
-        let v = 8 + 10 * (gray_idx - 232);
-        (v, v, v)
-    };
-    let gray_dist = dist2(r, g, b, gr, gg, gb);
+/// The 16 basic colors with the RGB values used for matching and export.
+const COLOR16_RGB: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkGrey, (128, 128, 128)),
+    (Color::Grey, (192, 192, 192)),
+    (Color::White, (255, 255, 255)),
+    (Color::DarkRed, (128, 0, 0)),
+    (Color::Red, (255, 0, 0)),
+    (Color::DarkGreen, (0, 128, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::DarkBlue, (0, 0, 128)),
+    (Color::Blue, (0, 0, 255)),
+    (Color::DarkCyan, (0, 128, 128)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::DarkMagenta, (128, 0, 128)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::DarkYellow, (128, 128, 0)),
+    (Color::Yellow, (255, 255, 0)),
+];
 
-    if gray_dist < cube_dist {
-        gray_idx
-    } else {
-        cube_idx
-    }
+fn rgb_to_color16(r: u8, g: u8, b: u8) -> Color {
+    static TABLE: OnceLock<Vec<(Color, Oklab)>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| {
+        COLOR16_RGB
+            .iter()
+            .map(|&(c, rgb)| (c, Oklab::from_rgb(rgb)))
+            .collect()
+    });
+    nearest(table, (r, g, b))
 }
 
-fn rgb_to_color16(r: u8, g: u8, b: u8) -> Color {
-    const TABLE: [(Color, (u8, u8, u8)); 16] = [
-        (Color::Black, (0, 0, 0)),
-        (Color::DarkGrey, (128, 128, 128)),
-        (Color::Grey, (192, 192, 192)),
-        (Color::White, (255, 255, 255)),
-        (Color::DarkRed, (128, 0, 0)),
-        (Color::Red, (255, 0, 0)),
-        (Color::DarkGreen, (0, 128, 0)),
-        (Color::Green, (0, 255, 0)),
-        (Color::DarkBlue, (0, 0, 128)),
-        (Color::Blue, (0, 0, 255)),
-        (Color::DarkCyan, (0, 128, 128)),
-        (Color::Cyan, (0, 255, 255)),
-        (Color::DarkMagenta, (128, 0, 128)),
-        (Color::Magenta, (255, 0, 255)),
-        (Color::DarkYellow, (128, 128, 0)),
-        (Color::Yellow, (255, 255, 0)),
+/// Approximate RGB value of a terminal color (xterm defaults for indexed
+/// colors); `None` for [`Color::Reset`], whose value only the terminal knows.
+pub fn color_to_rgb(c: Color) -> Option<(u8, u8, u8)> {
+    const ANSI16: [Color; 16] = [
+        Color::Black,
+        Color::DarkRed,
+        Color::DarkGreen,
+        Color::DarkYellow,
+        Color::DarkBlue,
+        Color::DarkMagenta,
+        Color::DarkCyan,
+        Color::Grey,
+        Color::DarkGrey,
+        Color::Red,
+        Color::Green,
+        Color::Yellow,
+        Color::Blue,
+        Color::Magenta,
+        Color::Cyan,
+        Color::White,
     ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
 
-    let mut best = Color::White;
-    let mut best_d = i32::MAX;
-    for (c, (cr, cg, cb)) in TABLE {
-        let d = dist2(r, g, b, cr, cg, cb);
-        if d < best_d {
-            best_d = d;
-            best = c;
+    match c {
+        Color::Reset => None,
+        Color::Rgb { r, g, b } => Some((r, g, b)),
+        Color::AnsiValue(n @ 0..=15) => color_to_rgb(ANSI16[n as usize]),
+        Color::AnsiValue(n @ 16..=231) => {
+            let i = (n - 16) as usize;
+            Some((
+                CUBE_LEVELS[i / 36],
+                CUBE_LEVELS[(i / 6) % 6],
+                CUBE_LEVELS[i % 6],
+            ))
+        }
+        Color::AnsiValue(n) => {
+            let v = 8 + 10 * (n - 232);
+            Some((v, v, v))
         }
+        named => COLOR16_RGB
+            .iter()
+            .find(|(c, _)| *c == named)
+            .map(|(_, rgb)| *rgb),
     }
-    best
+}
+
+/// `to` with each color `t` (0-1) of the way from the color at the same
+/// relative position in `from`, mixed in OKLab and matched to `mode`.
+pub fn crossfade(from: &[Color], to: &[Color], t: f32, mode: ColorMode) -> Vec<Color> {
+    let n = to.len();
+    to.iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let j = match (from.len(), n) {
+                (0, _) => return c,
+                (m, 1) => m - 1,
+                (m, n) => ((i * (m - 1)) as f32 / (n - 1) as f32).round() as usize,
+            };
+            let (Some(a), Some(b)) = (color_to_rgb(from[j]), color_to_rgb(c)) else {
+                return c;
+            };
+            let mixed = Oklab::from_rgb(a)
+                .lerp(Oklab::from_rgb(b), t.clamp(0.0, 1.0))
+                .to_rgb();
+            match mode {
+                ColorMode::Mono => c,
+                _ => quantize(mode, mixed),
+            }
+        })
+        .collect()
 }
 
 fn colors_from_rgb(mode: ColorMode, list: &[(u8, u8, u8)]) -> Vec<Color> {
@@ -112,12 +171,7 @@ fn colors_from_rgb(mode: ColorMode, list: &[(u8, u8, u8)]) -> Vec<Color> {
     }
 }
 
-fn lerp_u8(a: u8, b: u8, t: f32) -> u8 {
-    let a = a as f32;
-    let b = b as f32;
-    (a + (b - a) * t).round().clamp(0.0, 255.0) as u8
-}
-
+/// `steps` colors evenly spaced along `stops`, interpolated in OKLab.
 fn gradient_from_stops(stops: &[(u8, u8, u8)], steps: usize) -> Vec<(u8, u8, u8)> {
     if steps == 0 || stops.is_empty() {
         return Vec::new();
@@ -129,6 +183,7 @@ fn gradient_from_stops(stops: &[(u8, u8, u8)], steps: usize) -> Vec<(u8, u8, u8)
         return vec![stops[0]];
     }
 
+    let labs: Vec<Oklab> = stops.iter().map(|&c| Oklab::from_rgb(c)).collect();
     let segs = stops.len().saturating_sub(1);
     let mut out = Vec::with_capacity(steps);
     for i in 0..steps {
@@ -139,13 +194,8 @@ fn gradient_from_stops(stops: &[(u8, u8, u8)], steps: usize) -> Vec<(u8, u8, u8)
             seg = segs.saturating_sub(1);
         }
         let lt = pos - (seg as f32);
-        let (r0, g0, b0) = stops[seg];
-        let (r1, g1, b1) = stops[seg + 1];
-        out.push((
-            lerp_u8(r0, r1, lt),
-            lerp_u8(g0, g1, lt),
-            lerp_u8(b0, b1, lt),
-        ));
+        let c = labs[seg].lerp(labs[seg + 1], lt);
+        out.push(c.to_rgb());
     }
     out
 }
@@ -158,7 +208,137 @@ fn colors_from_stops(mode: ColorMode, stops: &[(u8, u8, u8)], steps: usize) -> V
     colors_from_rgb(mode, &rgb)
 }
 
-pub fn build_palette(scheme: ColorScheme, mode: ColorMode, default_background: bool) -> Palette {
+/// Global color adjustments applied on top of a theme, in OKLab. The
+/// default changes nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorAdjust {
+    /// Lightness multiplier (min 0.1 max 3).
+    pub brightness: f32,
+    /// Lightness spread around mid-gray (min 0.1 max 3).
+    pub contrast: f32,
+    /// Chroma multiplier, 0 for gray (min 0 max 3).
+    pub saturation: f32,
+    /// Hue rotation in degrees (-180 to 180).
+    pub hue_shift: f32,
+    /// Show the result as seen with a color-vision deficiency; applied last.
+    pub simulate_cvd: Option<Cvd>,
+}
+
+impl Default for ColorAdjust {
+    fn default() -> Self {
+        Self {
+            brightness: 1.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            hue_shift: 0.0,
+            simulate_cvd: None,
+        }
+    }
+}
+
+impl ColorAdjust {
+    pub fn is_neutral(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Clamped to the documented ranges, with the hue wrapped into -180..180.
+    pub fn clamped(self) -> Self {
+        let hue = (self.hue_shift + 180.0).rem_euclid(360.0) - 180.0;
+        Self {
+            brightness: self.brightness.clamp(0.1, 3.0),
+            contrast: self.contrast.clamp(0.1, 3.0),
+            saturation: self.saturation.clamp(0.0, 3.0),
+            hue_shift: if hue.is_finite() { hue } else { 0.0 },
+            ..self
+        }
+    }
+
+    fn apply(&self, rgb: (u8, u8, u8)) -> (u8, u8, u8) {
+        let neutral = Self::default();
+        let tuned = if (
+            self.brightness,
+            self.contrast,
+            self.saturation,
+            self.hue_shift,
+        ) == (
+            neutral.brightness,
+            neutral.contrast,
+            neutral.saturation,
+            neutral.hue_shift,
+        ) {
+            rgb
+        } else {
+            self.tune(rgb)
+        };
+        match self.simulate_cvd {
+            Some(cvd) => cvd.simulate(tuned),
+            None => tuned,
+        }
+    }
+
+    fn tune(&self, rgb: (u8, u8, u8)) -> (u8, u8, u8) {
+        let lab = Oklab::from_rgb(rgb);
+        let l = (((lab.l - 0.5) * self.contrast + 0.5) * self.brightness).clamp(0.0, 1.0);
+        let hue = lab.b.atan2(lab.a) + self.hue_shift.to_radians();
+        let c = lab.chroma();
+        Oklab {
+            l,
+            a: c * hue.cos(),
+            b: c * hue.sin(),
+        }
+        .with_chroma(c * self.saturation)
+        .to_rgb()
+    }
+}
+
+/// `color` with its OKLab hue turned by `degrees`, as `mode` shows it.
+pub(crate) fn rotate_hue(color: Color, degrees: f32, mode: ColorMode) -> Color {
+    let turn = ColorAdjust {
+        hue_shift: degrees,
+        ..ColorAdjust::default()
+    };
+    match color_to_rgb(color) {
+        Some(rgb) => quantize(mode, turn.tune(rgb)),
+        None => color,
+    }
+}
+
+/// `rgb` as the nearest color `mode` can show.
+pub(crate) fn quantize(mode: ColorMode, (r, g, b): (u8, u8, u8)) -> Color {
+    match mode {
+        ColorMode::Mono => Color::White,
+        ColorMode::TrueColor => Color::Rgb { r, g, b },
+        ColorMode::Color256 => Color::AnsiValue(rgb_to_ansi256(r, g, b)),
+        ColorMode::Color16 => rgb_to_color16(r, g, b),
+    }
+}
+
+/// Builds the palette for `scheme`, quantized for `mode`.
+pub fn build_palette(scheme: &ColorScheme, mode: ColorMode, default_background: bool) -> Palette {
+    build_adjusted_palette(scheme, mode, default_background, ColorAdjust::default())
+}
+
+/// Like [`build_palette`], with `adjust` applied to the theme colors (not
+/// the background) before they are matched to `mode`.
+pub fn build_adjusted_palette(
+    scheme: &ColorScheme,
+    mode: ColorMode,
+    default_background: bool,
+    adjust: ColorAdjust,
+) -> Palette {
+    let mut palette = base_palette(scheme, mode, default_background);
+    if !adjust.is_neutral() && mode != ColorMode::Mono {
+        for c in &mut palette.colors {
+            if let Some(rgb) = color_to_rgb(*c) {
+                *c = quantize(mode, adjust.apply(rgb));
+            }
+        }
+    }
+    palette
+}
+
+/// The theme's own palette, before any [`ColorAdjust`].
+fn base_palette(scheme: &ColorScheme, mode: ColorMode, default_background: bool) -> Palette {
     let mut bg = if default_background {
         None
     } else {
@@ -473,6 +653,71 @@ pub fn build_palette(scheme: ColorScheme, mode: ColorMode, default_background: b
             &[(0, 0, 0), (0, 10, 40), (0, 80, 160), (200, 120, 255)],
             9,
         ),
+        ColorScheme::Viridis => colors_from_stops(
+            mode,
+            &[
+                (68, 1, 84),
+                (59, 82, 139),
+                (33, 145, 140),
+                (94, 201, 98),
+                (253, 231, 37),
+            ],
+            9,
+        ),
+        ColorScheme::Cividis => colors_from_stops(
+            mode,
+            &[
+                (0, 32, 77),
+                (49, 68, 107),
+                (102, 105, 112),
+                (149, 143, 120),
+                (203, 186, 105),
+                (255, 234, 70),
+            ],
+            9,
+        ),
+        ColorScheme::Magma => colors_from_stops(
+            mode,
+            &[
+                (0, 0, 4),
+                (59, 15, 112),
+                (140, 41, 129),
+                (222, 73, 104),
+                (254, 159, 109),
+                (252, 253, 191),
+            ],
+            9,
+        ),
+        ColorScheme::Custom(theme) => {
+            let mut rgb = gradient_from_stops(&theme.stops, theme.steps);
+            rgb.extend(theme.head);
+            colors_from_rgb(mode, &rgb)
+        }
+        ColorScheme::Rgb(r, g, b) => colors_from_rgb(mode, &lightness_ramp((*r, *g, *b), 9)),
+        ColorScheme::Blend(parts) => {
+            // Part k of n supplies the k-th slice of its own ramp, so the
+            // first theme colors the tails and the last one the heads.
+            let n = parts.len();
+            let mut colors = Vec::new();
+            for (k, part) in parts.iter().enumerate() {
+                let p = base_palette(part, mode, default_background);
+                if k == 0 {
+                    bg = p.bg;
+                }
+                let m = p.colors.len();
+                let (start, end) = (k * m / n, ((k + 1) * m / n).max(k * m / n + 1));
+                colors.extend(p.colors.get(start..end.min(m)).unwrap_or_default());
+            }
+            if mode == ColorMode::Mono || colors.is_empty() {
+                vec![Color::White]
+            } else {
+                colors
+            }
+        }
+        ColorScheme::Mix(parts) => match parts.first() {
+            Some(first) => return base_palette(first, mode, default_background),
+            None => return base_palette(&ColorScheme::Green, mode, default_background),
+        },
     };
 
     if default_background {
@@ -481,3 +726,178 @@ pub fn build_palette(scheme: ColorScheme, mode: ColorMode, default_background: b
 
     Palette { colors, bg }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::runtime::all_color_schemes;
+
+    fn lightness(colors: &[Color]) -> Vec<f32> {
+        colors
+            .iter()
+            .map(|&c| Oklab::from_rgb(color_to_rgb(c).expect("concrete color")).l)
+            .collect()
+    }
+
+    #[test]
+    fn built_in_themes_are_brightness_ramps() {
+        use ColorMode::{Color16, Color256, TrueColor};
+
+        const ALL: &[ColorMode] = &[TrueColor, Color256, Color16];
+        // Hand-picked lists that are not dark-to-bright in some modes. The
+        // test also fails if one of these becomes a ramp, so the list stays
+        // accurate.
+        let exceptions: &[(ColorScheme, &[ColorMode])] = &[
+            // Hue walks and pastel tints, picked for color, not brightness.
+            (ColorScheme::Rainbow, ALL),
+            (ColorScheme::Vaporwave, ALL),
+            (ColorScheme::Aurora, ALL),
+            (ColorScheme::Spectrum20, ALL),
+            // White heads followed by a cyan or pink glint.
+            (ColorScheme::Snow, ALL),
+            (ColorScheme::FancyDiamond, ALL),
+            // The dark gray tail (234) is lighter than the navy after it.
+            (ColorScheme::Blue, &[TrueColor, Color256]),
+            // 16-color lists that step down: DarkGrey before DarkGreen or
+            // DarkYellow, Red before DarkYellow, Magenta/Red/Blue in Nebula.
+            (ColorScheme::Green2, &[Color16]),
+            (ColorScheme::Gold, &[Color16]),
+            (ColorScheme::Fire, &[Color16]),
+            (ColorScheme::Nebula, &[Color16]),
+        ];
+
+        for scheme in all_color_schemes() {
+            for &mode in ALL {
+                let l = lightness(&build_palette(scheme, mode, false).colors);
+                let ramp = l.windows(2).all(|w| w[0] <= w[1]);
+                let excepted = exceptions
+                    .iter()
+                    .any(|(s, modes)| s == scheme && modes.contains(&mode));
+                assert_eq!(ramp, !excepted, "{:?} in {:?}: {:?}", scheme, mode, l);
+            }
+        }
+    }
+
+    #[test]
+    fn compound_themes_split_or_mix_their_parts() {
+        use crate::runtime::parse_color_scheme;
+
+        let blend = parse_color_scheme("Fire + Ocean").unwrap();
+        assert_eq!(
+            blend,
+            ColorScheme::Blend([ColorScheme::Fire, ColorScheme::Ocean].into())
+        );
+        assert_eq!(parse_color_scheme("fire+ocean").as_ref(), Ok(&blend));
+
+        let fire = build_palette(&ColorScheme::Fire, ColorMode::TrueColor, false).colors;
+        let ocean = build_palette(&ColorScheme::Ocean, ColorMode::TrueColor, false).colors;
+        let colors = build_palette(&blend, ColorMode::TrueColor, false).colors;
+        assert_eq!(colors.first(), fire.first());
+        assert_eq!(colors.last(), ocean.last());
+        assert_eq!(
+            colors.len(),
+            fire.len() / 2 + (ocean.len() - ocean.len() / 2)
+        );
+
+        let mix = parse_color_scheme("mix:neon,#00ff00,rgb(255,200,0)").unwrap();
+        assert!(matches!(mix, ColorScheme::Mix(_)));
+        assert_eq!(
+            build_palette(&mix, ColorMode::TrueColor, false).colors,
+            build_palette(&ColorScheme::Neon, ColorMode::TrueColor, false).colors
+        );
+
+        for bad in ["fire+", "mix:fire", "fire+mix:a,b", "fire+nope"] {
+            assert!(parse_color_scheme(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn single_colors_parse_from_hex_or_rgb() {
+        use crate::runtime::parse_color_scheme;
+
+        assert_eq!(
+            parse_color_scheme("#0077cc"),
+            Ok(ColorScheme::Rgb(0x00, 0x77, 0xcc))
+        );
+        assert_eq!(
+            parse_color_scheme("#FFaa00"),
+            Ok(ColorScheme::Rgb(255, 170, 0))
+        );
+        assert_eq!(
+            parse_color_scheme("rgb(0, 119, 204)"),
+            Ok(ColorScheme::Rgb(0, 119, 204))
+        );
+        assert_eq!(
+            parse_color_scheme("RGB(255,0,0)"),
+            Ok(ColorScheme::Rgb(255, 0, 0))
+        );
+
+        for bad in ["#0077c", "#0077ccd", "#00gg00", "#"] {
+            let err = parse_color_scheme(bad).unwrap_err();
+            assert!(err.contains("expected #RRGGBB"), "{}: {}", bad, err);
+        }
+        for bad in [
+            "rgb(256,0,0)",
+            "rgb(-1,0,0)",
+            "rgb(1,2)",
+            "rgb(1,2,3,4)",
+            "rgb(a,b,c)",
+        ] {
+            let err = parse_color_scheme(bad).unwrap_err();
+            assert!(err.contains("values 0-255"), "{}: {}", bad, err);
+        }
+    }
+
+    #[test]
+    fn color_adjustments_change_lightness_chroma_and_hue() {
+        let build = |adjust| {
+            build_adjusted_palette(&ColorScheme::Ocean, ColorMode::TrueColor, false, adjust)
+        };
+        let base = build(ColorAdjust::default());
+        assert_eq!(
+            base.colors,
+            build_palette(&ColorScheme::Ocean, ColorMode::TrueColor, false).colors
+        );
+
+        let brighter = build(ColorAdjust {
+            brightness: 1.5,
+            ..ColorAdjust::default()
+        });
+        let (l0, l1) = (lightness(&base.colors), lightness(&brighter.colors));
+        assert!(l0.iter().zip(&l1).all(|(a, b)| b >= a), "{:?} {:?}", l0, l1);
+        assert_eq!(brighter.bg, base.bg);
+
+        let gray = build(ColorAdjust {
+            saturation: 0.0,
+            ..ColorAdjust::default()
+        });
+        for c in gray.colors {
+            let (r, g, b) = color_to_rgb(c).unwrap();
+            assert!(r.abs_diff(g) <= 1 && g.abs_diff(b) <= 1, "{:?}", c);
+        }
+
+        let red = Oklab::from_rgb((200, 0, 0));
+        let shifted = Oklab::from_rgb(
+            ColorAdjust {
+                hue_shift: 180.0,
+                ..ColorAdjust::default()
+            }
+            .apply((200, 0, 0)),
+        );
+        assert!(red.a > 0.0 && shifted.a < 0.0, "{:?}", shifted);
+        assert_eq!(
+            ColorAdjust {
+                hue_shift: 200.0,
+                brightness: 9.0,
+                ..ColorAdjust::default()
+            }
+            .clamped(),
+            ColorAdjust {
+                hue_shift: -160.0,
+                brightness: 3.0,
+                ..ColorAdjust::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,232 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! `cosmostrix play`: replays an asciicast recording through a [`Backend`].
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+
+use cosmostrix::ansi::VirtualScreen;
+use cosmostrix::cast::{Cast, CastEvent};
+use cosmostrix::terminal::Backend;
+use cosmostrix::{Cell, Frame};
+
+pub const MIN_SPEED: f64 = 1.0 / 16.0;
+pub const MAX_SPEED: f64 = 16.0;
+const SEEK_STEP_S: f64 = 5.0;
+const IDLE_POLL: Duration = Duration::from_millis(250);
+
+pub struct PlayOptions {
+    pub speed: f64,
+    pub looping: bool,
+    /// Without `looping`, keep showing the last frame until a key is pressed
+    /// instead of returning as soon as the recording ends.
+    pub hold: bool,
+}
+
+/// Replay position: the virtual screen after the first `next` events.
+struct Cursor<'a> {
+    cast: &'a Cast,
+    screen: VirtualScreen,
+    next: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(cast: &'a Cast) -> Self {
+        Self {
+            cast,
+            screen: VirtualScreen::new(cast.width, cast.height),
+            next: 0,
+        }
+    }
+
+    /// Applies every event up to `pos`; true if any was applied.
+    fn advance(&mut self, pos: f64) -> bool {
+        let start = self.next;
+        while let Some((t, ev)) = self.cast.events.get(self.next) {
+            if *t > pos {
+                break;
+            }
+            match ev {
+                CastEvent::Output(s) => self.screen.feed(s),
+                CastEvent::Resize(w, h) => self.screen.resize(*w, *h),
+            }
+            self.next += 1;
+        }
+        self.next != start
+    }
+
+    fn next_time(&self) -> Option<f64> {
+        self.cast.events.get(self.next).map(|(t, _)| *t)
+    }
+}
+
+/// Copies `screen` onto `frame`, centered; cropped when the recording is
+/// larger than the terminal and padded with blanks when it is smaller.
+fn compose(screen: &Frame, frame: &mut Frame) {
+    let off_x = (frame.width as i32 - screen.width as i32) / 2;
+    let off_y = (frame.height as i32 - screen.height as i32) / 2;
+    let blank = Cell::blank_with_bg(None);
+    for y in 0..frame.height {
+        for x in 0..frame.width {
+            let sx = x as i32 - off_x;
+            let sy = y as i32 - off_y;
+            let cell = if sx >= 0 && sy >= 0 {
+                screen.get(sx as u16, sy as u16).copied().unwrap_or(blank)
+            } else {
+                blank
+            };
+            frame.set(x, y, cell);
+        }
+    }
+}
+
+/// Plays `cast` with its original timing until it ends (or forever with
+/// `looping`) or the user quits.
+///
+/// Keys: q/Esc/Ctrl+C quit (any key once a held recording has ended),
+/// Space/p pause, Up/Down (or +/-) double or halve the
+/// speed, Left/Right seek 5 seconds, Home restarts.
+pub fn play<B: Backend>(term: &mut B, cast: &Cast, opts: PlayOptions) -> std::io::Result<()> {
+    let (w, h) = term.size()?;
+    let mut frame = Frame::new(w, h, None);
+    let mut cur = Cursor::new(cast);
+    let mut speed = opts.speed.clamp(MIN_SPEED, MAX_SPEED);
+    let mut paused = false;
+    let mut pos = 0.0f64;
+    let mut last_tick = Instant::now();
+    let mut redraw = true;
+
+    loop {
+        let now = Instant::now();
+        if !paused {
+            pos += now.duration_since(last_tick).as_secs_f64() * speed;
+        }
+        last_tick = now;
+
+        if cur.advance(pos) || redraw {
+            compose(cur.screen.frame(), &mut frame);
+            term.draw(&mut frame)?;
+            redraw = false;
+        }
+
+        let timeout = match cur.next_time() {
+            None if opts.looping && !cast.events.is_empty() => {
+                cur = Cursor::new(cast);
+                pos = 0.0;
+                continue;
+            }
+            None if opts.hold => IDLE_POLL,
+            None => return Ok(()),
+            Some(_) if paused => IDLE_POLL,
+            Some(t) => Duration::from_secs_f64(((t - pos) / speed).max(0.0)).min(IDLE_POLL),
+        };
+
+        if !term.poll_event(timeout)? {
+            continue;
+        }
+        while term.poll_event(Duration::ZERO)? {
+            match term.read_event()? {
+                Event::Resize(nw, nh) => {
+                    frame = Frame::new(nw, nh, None);
+                    redraw = true;
+                }
+                Event::Key(k) if k.kind == KeyEventKind::Press && cur.next_time().is_none() => {
+                    return Ok(());
+                }
+                Event::Key(k) if k.kind == KeyEventKind::Press => match k.code {
+                    KeyCode::Esc | KeyCode::Char('q') => return Ok(()),
+                    KeyCode::Char('c') if k.modifiers.contains(KeyModifiers::CONTROL) => {
+                        return Ok(())
+                    }
+                    KeyCode::Char(' ') | KeyCode::Char('p') => paused = !paused,
+                    KeyCode::Up | KeyCode::Char('+') | KeyCode::Char('=') => {
+                        speed = (speed * 2.0).min(MAX_SPEED)
+                    }
+                    KeyCode::Down | KeyCode::Char('-') => speed = (speed / 2.0).max(MIN_SPEED),
+                    KeyCode::Right => pos = (pos + SEEK_STEP_S).min(cast.duration()),
+                    KeyCode::Left | KeyCode::Home => {
+                        pos = if k.code == KeyCode::Home {
+                            0.0
+                        } else {
+                            (pos - SEEK_STEP_S).max(0.0)
+                        };
+                        cur = Cursor::new(cast);
+                        redraw = true;
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmostrix::cast::parse_cast;
+    use cosmostrix::terminal::MemoryBackend;
+
+    #[test]
+    fn pads_a_small_recording_into_the_terminal() {
+        let cast = parse_cast(
+            "{\"version\": 2, \"width\": 4, \"height\": 2}\n\
+             [0.0, \"o\", \"\\u001b[2J\\u001b[1;1Hab\"]\n\
+             [0.01, \"o\", \"\\u001b[2;4Hz\"]\n",
+        )
+        .unwrap();
+        let mut term = MemoryBackend::new(8, 4);
+        play(
+            &mut term,
+            &cast,
+            PlayOptions {
+                speed: 4.0,
+                looping: false,
+                hold: false,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(term.cell(2, 1).map(|c| c.ch), Some('a'));
+        assert_eq!(term.cell(3, 1).map(|c| c.ch), Some('b'));
+        assert_eq!(term.cell(5, 2).map(|c| c.ch), Some('z'));
+        assert_eq!(term.cell(0, 0).map(|c| c.ch), Some(' '));
+    }
+
+    #[test]
+    fn ctrl_c_quits_and_the_last_frame_is_held_for_a_key() {
+        use crossterm::event::KeyEvent;
+
+        let cast = parse_cast(
+            "{\"version\": 2, \"width\": 2, \"height\": 1}\n\
+             [0.0, \"o\", \"a\"]\n\
+             [100.0, \"o\", \"b\"]\n",
+        )
+        .unwrap();
+        let opts = || PlayOptions {
+            speed: 1.0,
+            looping: false,
+            hold: true,
+        };
+
+        let mut term = MemoryBackend::new(2, 1);
+        term.push_event(Event::Key(KeyEvent::new(
+            KeyCode::Char('c'),
+            KeyModifiers::CONTROL,
+        )));
+        play(&mut term, &cast, opts()).unwrap();
+        assert_eq!(term.cell(0, 0).map(|c| c.ch), Some('a'));
+        assert_eq!(term.cell(1, 0).map(|c| c.ch), Some(' '));
+
+        // Past the last event, any key ends a held replay.
+        let cast =
+            parse_cast("{\"version\": 2, \"width\": 2, \"height\": 1}\n[0.0, \"o\", \"a\"]\n")
+                .unwrap();
+        let mut term = MemoryBackend::new(2, 1);
+        term.push_event(Event::Key(KeyEvent::from(KeyCode::Char('x'))));
+        play(&mut term, &cast, opts()).unwrap();
+        assert_eq!(term.cell(0, 0).map(|c| c.ch), Some('a'));
+    }
+}
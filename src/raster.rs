@@ -0,0 +1,326 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Rasterizes a [`Frame`] to an RGB image with a built-in bitmap font, for
+//! headless export (`--export-frames`).
+//!
+//! Printable ASCII uses a classic 5x8 font and braille is drawn as dots. Any
+//! other character (katakana, symbols, ...) gets a stable pseudo-glyph derived
+//! from its code point, so the rain keeps its texture without shipping a
+//! full Unicode font.
+
+use std::io::{Result, Write};
+
+use crossterm::style::Color;
+
+use crate::frame::Frame;
+use crate::palette::color_to_rgb;
+
+/// Foreground used for cells without one (`Cell.fg == None`).
+pub const DEFAULT_FG: (u8, u8, u8) = (204, 204, 204);
+/// Background used for cells without one (`Cell.bg == None`).
+pub const DEFAULT_BG: (u8, u8, u8) = (0, 0, 0);
+
+/// Largest image side, in pixels, that [`image_size`] allows.
+pub const MAX_IMAGE_SIDE: u32 = 16384;
+
+/// Glyph box in font pixels: 5 columns plus spacing, 8 rows plus spacing.
+const BOX_W: u32 = 6;
+const BOX_H: u32 = 10;
+
+/// Column-major 5x8 glyphs for 0x20..=0x7E; bit 0 is the top row.
+const FONT_5X8: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00],
+    [0x00, 0x00, 0x5F, 0x00, 0x00],
+    [0x00, 0x07, 0x00, 0x07, 0x00],
+    [0x14, 0x7F, 0x14, 0x7F, 0x14],
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12],
+    [0x23, 0x13, 0x08, 0x64, 0x62],
+    [0x36, 0x49, 0x56, 0x20, 0x50],
+    [0x00, 0x08, 0x07, 0x03, 0x00],
+    [0x00, 0x1C, 0x22, 0x41, 0x00],
+    [0x00, 0x41, 0x22, 0x1C, 0x00],
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A],
+    [0x08, 0x08, 0x3E, 0x08, 0x08],
+    [0x00, 0x80, 0x70, 0x30, 0x00],
+    [0x08, 0x08, 0x08, 0x08, 0x08],
+    [0x00, 0x00, 0x60, 0x60, 0x00],
+    [0x20, 0x10, 0x08, 0x04, 0x02],
+    [0x3E, 0x51, 0x49, 0x45, 0x3E],
+    [0x00, 0x42, 0x7F, 0x40, 0x00],
+    [0x72, 0x49, 0x49, 0x49, 0x46],
+    [0x21, 0x41, 0x49, 0x4D, 0x33],
+    [0x18, 0x14, 0x12, 0x7F, 0x10],
+    [0x27, 0x45, 0x45, 0x45, 0x39],
+    [0x3C, 0x4A, 0x49, 0x49, 0x31],
+    [0x41, 0x21, 0x11, 0x09, 0x07],
+    [0x36, 0x49, 0x49, 0x49, 0x36],
+    [0x46, 0x49, 0x49, 0x29, 0x1E],
+    [0x00, 0x00, 0x14, 0x00, 0x00],
+    [0x00, 0x40, 0x34, 0x00, 0x00],
+    [0x00, 0x08, 0x14, 0x22, 0x41],
+    [0x14, 0x14, 0x14, 0x14, 0x14],
+    [0x00, 0x41, 0x22, 0x14, 0x08],
+    [0x02, 0x01, 0x59, 0x09, 0x06],
+    [0x3E, 0x41, 0x5D, 0x59, 0x4E],
+    [0x7C, 0x12, 0x11, 0x12, 0x7C],
+    [0x7F, 0x49, 0x49, 0x49, 0x36],
+    [0x3E, 0x41, 0x41, 0x41, 0x22],
+    [0x7F, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x49, 0x49, 0x49, 0x41],
+    [0x7F, 0x09, 0x09, 0x09, 0x01],
+    [0x3E, 0x41, 0x41, 0x51, 0x73],
+    [0x7F, 0x08, 0x08, 0x08, 0x7F],
+    [0x00, 0x41, 0x7F, 0x41, 0x00],
+    [0x20, 0x40, 0x41, 0x3F, 0x01],
+    [0x7F, 0x08, 0x14, 0x22, 0x41],
+    [0x7F, 0x40, 0x40, 0x40, 0x40],
+    [0x7F, 0x02, 0x1C, 0x02, 0x7F],
+    [0x7F, 0x04, 0x08, 0x10, 0x7F],
+    [0x3E, 0x41, 0x41, 0x41, 0x3E],
+    [0x7F, 0x09, 0x09, 0x09, 0x06],
+    [0x3E, 0x41, 0x51, 0x21, 0x5E],
+    [0x7F, 0x09, 0x19, 0x29, 0x46],
+    [0x26, 0x49, 0x49, 0x49, 0x32],
+    [0x03, 0x01, 0x7F, 0x01, 0x03],
+    [0x3F, 0x40, 0x40, 0x40, 0x3F],
+    [0x1F, 0x20, 0x40, 0x20, 0x1F],
+    [0x3F, 0x40, 0x38, 0x40, 0x3F],
+    [0x63, 0x14, 0x08, 0x14, 0x63],
+    [0x03, 0x04, 0x78, 0x04, 0x03],
+    [0x61, 0x59, 0x49, 0x4D, 0x43],
+    [0x00, 0x7F, 0x41, 0x41, 0x41],
+    [0x02, 0x04, 0x08, 0x10, 0x20],
+    [0x00, 0x41, 0x41, 0x41, 0x7F],
+    [0x04, 0x02, 0x01, 0x02, 0x04],
+    [0x40, 0x40, 0x40, 0x40, 0x40],
+    [0x00, 0x03, 0x07, 0x08, 0x00],
+    [0x20, 0x54, 0x54, 0x78, 0x40],
+    [0x7F, 0x28, 0x44, 0x44, 0x38],
+    [0x38, 0x44, 0x44, 0x44, 0x28],
+    [0x38, 0x44, 0x44, 0x28, 0x7F],
+    [0x38, 0x54, 0x54, 0x54, 0x18],
+    [0x00, 0x08, 0x7E, 0x09, 0x02],
+    [0x18, 0xA4, 0xA4, 0x9C, 0x78],
+    [0x7F, 0x08, 0x04, 0x04, 0x78],
+    [0x00, 0x44, 0x7D, 0x40, 0x00],
+    [0x20, 0x40, 0x40, 0x3D, 0x00],
+    [0x7F, 0x10, 0x28, 0x44, 0x00],
+    [0x00, 0x41, 0x7F, 0x40, 0x00],
+    [0x7C, 0x04, 0x78, 0x04, 0x78],
+    [0x7C, 0x08, 0x04, 0x04, 0x78],
+    [0x38, 0x44, 0x44, 0x44, 0x38],
+    [0xFC, 0x18, 0x24, 0x24, 0x18],
+    [0x18, 0x24, 0x24, 0x18, 0xFC],
+    [0x7C, 0x08, 0x04, 0x04, 0x08],
+    [0x48, 0x54, 0x54, 0x54, 0x24],
+    [0x04, 0x04, 0x3F, 0x44, 0x24],
+    [0x3C, 0x40, 0x40, 0x20, 0x7C],
+    [0x1C, 0x20, 0x40, 0x20, 0x1C],
+    [0x3C, 0x40, 0x30, 0x40, 0x3C],
+    [0x44, 0x28, 0x10, 0x28, 0x44],
+    [0x4C, 0x90, 0x90, 0x90, 0x7C],
+    [0x44, 0x64, 0x54, 0x4C, 0x44],
+    [0x00, 0x08, 0x36, 0x41, 0x00],
+    [0x00, 0x00, 0x77, 0x00, 0x00],
+    [0x00, 0x41, 0x36, 0x08, 0x00],
+    [0x02, 0x01, 0x02, 0x04, 0x02],
+];
+
+/// An 8-bit RGB image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major `R, G, B` bytes.
+    pub rgb: Vec<u8>,
+}
+
+impl Image {
+    /// A `width` x `height` image filled with `color`.
+    pub fn new(width: u32, height: u32, color: (u8, u8, u8)) -> Self {
+        let (r, g, b) = color;
+        Self {
+            width,
+            height,
+            rgb: [r, g, b].repeat(width as usize * height as usize),
+        }
+    }
+
+    /// The pixel at `(x, y)`.
+    pub fn pixel(&self, x: u32, y: u32) -> (u8, u8, u8) {
+        let i = (y as usize * self.width as usize + x as usize) * 3;
+        (self.rgb[i], self.rgb[i + 1], self.rgb[i + 2])
+    }
+
+    /// Writes a binary PPM (P6).
+    pub fn write_ppm<W: Write>(&self, mut out: W) -> Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.rgb)?;
+        out.flush()
+    }
+
+    /// Writes an 8-bit RGB PNG.
+    pub fn write_png<W: Write>(&self, out: W) -> Result<()> {
+        let mut enc = png::Encoder::new(out, self.width, self.height);
+        enc.set_color(png::ColorType::Rgb);
+        enc.set_depth(png::BitDepth::Eight);
+        let mut writer = enc.write_header()?;
+        writer.write_image_data(&self.rgb)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// 5x8 bitmap (column-major, bit 0 = top) for `ch`.
+fn glyph(ch: char) -> [u8; 5] {
+    let cp = ch as u32;
+    match cp {
+        0x20..=0x7E => FONT_5X8[(cp - 0x20) as usize],
+        0x2800..=0x28FF => {
+            // Braille: dots 1-3 and 7 in the left column, 4-6 and 8 in the
+            // right one, top to bottom.
+            let bits = cp - 0x2800;
+            let dot = |b: u32, row: u32| {
+                if bits & (1 << b) != 0 {
+                    0b11 << (row * 2)
+                } else {
+                    0
+                }
+            };
+            let left = dot(0, 0) | dot(1, 1) | dot(2, 2) | dot(6, 3);
+            let right = dot(3, 0) | dot(4, 1) | dot(5, 2) | dot(7, 3);
+            [left as u8, left as u8, 0, right as u8, right as u8]
+        }
+        _ if ch.is_whitespace() => [0; 5],
+        _ => {
+            // xorshift on the code point: a stable, glyph-like 5x7 pattern.
+            let mut h = cp.wrapping_mul(0x9E37_79B9) ^ 0x5bd1_e995;
+            let mut cols = [0u8; 5];
+            for c in cols.iter_mut() {
+                h ^= h << 13;
+                h ^= h >> 17;
+                h ^= h << 5;
+                *c = (h & 0x7F) as u8;
+            }
+            // Give it a vertical stroke so it reads as a character.
+            cols[2] |= 0x3E;
+            cols
+        }
+    }
+}
+
+fn rgb_or(c: Option<Color>, default: (u8, u8, u8)) -> (u8, u8, u8) {
+    c.and_then(color_to_rgb).unwrap_or(default)
+}
+
+/// Pixel size of a `cols` x `lines` frame drawn with `cell_w` x `cell_h`
+/// cells, or an error if either side is over [`MAX_IMAGE_SIDE`].
+pub fn image_size(
+    cols: u16,
+    lines: u16,
+    cell_w: u32,
+    cell_h: u32,
+) -> std::result::Result<(u32, u32), String> {
+    let w = cols as u64 * cell_w as u64;
+    let h = lines as u64 * cell_h as u64;
+    if w > MAX_IMAGE_SIDE as u64 || h > MAX_IMAGE_SIDE as u64 {
+        return Err(format!(
+            "image too large: {}x{} px (max {})",
+            w, h, MAX_IMAGE_SIDE
+        ));
+    }
+    Ok((w as u32, h as u32))
+}
+
+/// Draws every cell of `frame` as a `cell_w` x `cell_h` pixel block.
+///
+/// Glyphs are scaled from the font with nearest-neighbor sampling; bold cells
+/// are drawn one font pixel wider.
+pub fn rasterize(frame: &Frame, cell_w: u32, cell_h: u32) -> Image {
+    rasterize_region(frame, cell_w, cell_h, 0, 0, frame.width, frame.height)
+}
+
+/// Like [`rasterize`], but only the `cols` x `lines` cells starting at
+/// `(x0, y0)` (clamped to the frame).
+pub fn rasterize_region(
+    frame: &Frame,
+    cell_w: u32,
+    cell_h: u32,
+    x0: u16,
+    y0: u16,
+    cols: u16,
+    lines: u16,
+) -> Image {
+    let cell_w = cell_w.max(1);
+    let cell_h = cell_h.max(1);
+    let x0 = x0.min(frame.width);
+    let y0 = y0.min(frame.height);
+    let cols = cols.min(frame.width - x0);
+    let lines = lines.min(frame.height - y0);
+    let mut img = Image::new(cols as u32 * cell_w, lines as u32 * cell_h, DEFAULT_BG);
+    let row_len = img.width as usize * 3;
+
+    for cy in 0..lines {
+        for cx in 0..cols {
+            let idx = (y0 + cy) as usize * frame.width as usize + (x0 + cx) as usize;
+            let cell = frame.cell_at_index(idx);
+            let fg = rgb_or(cell.fg, DEFAULT_FG);
+            let bg = rgb_or(cell.bg, DEFAULT_BG);
+            let bits = glyph(cell.ch);
+
+            for py in 0..cell_h {
+                let gy = py * BOX_H / cell_h;
+                let row = gy.checked_sub(1).filter(|&r| r < 8);
+                let base = (cy as u32 * cell_h + py) as usize * row_len;
+                for px in 0..cell_w {
+                    let gx = (px * BOX_W / cell_w) as usize;
+                    let on = row.is_some_and(|r| {
+                        let lit = |x: usize| x < 5 && bits[x] & (1 << r) != 0;
+                        lit(gx) || (cell.bold && gx > 0 && lit(gx - 1))
+                    });
+                    let (r, g, b) = if on { fg } else { bg };
+                    let i = base + (cx as u32 * cell_w + px) as usize * 3;
+                    img.rgb[i..i + 3].copy_from_slice(&[r, g, b]);
+                }
+            }
+        }
+    }
+    img
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::cell::Cell;
+
+    #[test]
+    fn draws_glyphs_with_cell_colors() {
+        let mut frame = Frame::new(2, 1, None);
+        frame.set(
+            0,
+            0,
+            Cell {
+                ch: '|',
+                fg: Some(Color::Rgb { r: 0, g: 255, b: 0 }),
+                bg: Some(Color::AnsiValue(196)),
+                bold: false,
+            },
+        );
+        let img = rasterize(&frame, 6, 10);
+
+        assert_eq!((img.width, img.height), (12, 10));
+        // '|' is a single column in the middle of the glyph.
+        assert_eq!(img.pixel(2, 5), (0, 255, 0));
+        assert_eq!(img.pixel(0, 5), (255, 0, 0));
+        // The second cell is blank, on the default background.
+        assert!((6..12).all(|x| img.pixel(x, 5) == DEFAULT_BG));
+        assert_eq!(image_size(2, 1, 6, 10), Ok((12, 10)));
+        assert!(image_size(1000, 24, 64, 16).is_err());
+
+        let mut ppm = Vec::new();
+        img.write_ppm(&mut ppm).unwrap();
+        assert!(ppm.starts_with(b"P6\n12 10\n255\n"));
+        assert_eq!(ppm.len(), 13 + 12 * 10 * 3);
+    }
+}
@@ -0,0 +1,662 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! The interactive loop: input handling, pacing and drawing against any
+//! [`Backend`].
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+
+#[cfg(unix)]
+use signal_hook::{consts::SIGSTOP, low_level};
+
+use crate::charset::all_charset_presets;
+use crate::runtime::{cycle_color_scheme_in, cycle_shading_mode};
+use crate::script::is_wide;
+use crate::terminal::Backend;
+use crate::{weighted_chars_from_str, Cloud, ColorAdjust, ColorScheme, Frame};
+
+/// Loop settings that are not part of the [`Cloud`] itself.
+pub struct RunOptions {
+    /// Time between frames (1 / fps).
+    pub target_period: Duration,
+    /// Stop once this passes.
+    pub end_time: Option<Instant>,
+    /// Quit on the first key press.
+    pub screensaver: bool,
+    /// `--fullwidth` as given; wide charsets turn it on regardless.
+    pub fullwidth: bool,
+    /// Droplet density before [`effective_density`] scales it to the screen.
+    pub base_density: f32,
+    pub density_auto: bool,
+    /// Charset expression the rain started with. `s`/`S` step from it
+    /// through the presets and back; each is rebuilt with `user_ranges` added.
+    pub charset: String,
+    pub user_ranges: Vec<(char, char)>,
+    pub def_ascii: bool,
+    /// Themes `c`/`C` step through: the built-ins, then any custom ones.
+    pub color_schemes: Vec<ColorScheme>,
+    pub color_cycle: Option<ColorCycle>,
+    /// Set by the SIGTSTP/SIGCONT handler; when present, Ctrl+Z suspends and
+    /// the loop calls [`Backend::resume`] once the flag is raised.
+    pub term_reinit: Option<Arc<AtomicBool>>,
+}
+
+/// Automatic theme changes (`--cycle-colors`).
+pub struct ColorCycle {
+    pub interval: Duration,
+    /// Themes in order; when the current one is not listed, the first is next.
+    pub themes: Vec<ColorScheme>,
+    pub crossfade: Duration,
+}
+
+impl ColorCycle {
+    fn next_after(&self, current: &ColorScheme) -> Option<ColorScheme> {
+        let next = match self.themes.iter().position(|t| t == current) {
+            Some(i) => i + 1,
+            None => 0,
+        };
+        self.themes.get(next % self.themes.len().max(1)).cloned()
+    }
+
+    /// Crossfade length, never longer than the interval.
+    fn fade(&self) -> Duration {
+        self.crossfade.min(self.interval)
+    }
+}
+
+/// Countdown to the next [`ColorCycle`] change; it does not run down while
+/// paused.
+struct CycleTimer {
+    left: Duration,
+    tick: Instant,
+}
+
+impl CycleTimer {
+    fn new(cycle: &ColorCycle, now: Instant) -> Self {
+        Self {
+            left: cycle.interval,
+            tick: now,
+        }
+    }
+
+    /// Advances the countdown to `now`. When it runs out, restarts it and
+    /// returns the theme after `current` with the crossfade to use.
+    fn poll(
+        &mut self,
+        cycle: &ColorCycle,
+        current: &ColorScheme,
+        paused: bool,
+        now: Instant,
+    ) -> Option<(ColorScheme, Duration)> {
+        let elapsed = now.saturating_duration_since(self.tick);
+        self.tick = now;
+        if paused {
+            return None;
+        }
+        if elapsed < self.left {
+            self.left -= elapsed;
+            return None;
+        }
+        self.left = cycle.interval;
+        cycle.next_after(current).map(|next| (next, cycle.fade()))
+    }
+
+    /// Starts a full interval from `now`, so a theme picked by hand stays
+    /// up as long as one picked by the cycle.
+    fn restart(&mut self, cycle: &ColorCycle, now: Instant) {
+        *self = Self::new(cycle, now);
+    }
+}
+
+/// Per-frame timing totals, printed by `--perf-stats`.
+#[derive(Debug, Default)]
+pub struct PerfStats {
+    pub frames: u64,
+    pub drawn_frames: u64,
+    pub work_sum_s: f64,
+    pub work_max_s: f32,
+    pub pressure_sum: f64,
+    pub pressure_max: f32,
+    pub overshoot_frames: u64,
+}
+
+fn auto_density_factor(cols: u16, lines: u16, fullwidth: bool) -> f32 {
+    let eff_cols = if fullwidth {
+        (cols / 2).max(1)
+    } else {
+        cols.max(1)
+    } as f32;
+    let eff_lines = lines.max(1) as f32;
+
+    let area = eff_cols * eff_lines;
+    let base = 80.0 * 25.0;
+    let factor = (area / base).sqrt();
+    factor.clamp(0.5, 2.0)
+}
+
+/// `base` density clamped to 0.01-5, scaled with the screen area when
+/// `auto` is set.
+pub fn effective_density(base: f32, cols: u16, lines: u16, fullwidth: bool, auto: bool) -> f32 {
+    let base = base.clamp(0.01, 5.0);
+    if !auto {
+        return base;
+    }
+    (base * auto_density_factor(cols, lines, fullwidth)).clamp(0.01, 5.0)
+}
+
+/// Charsets `s`/`S` step through: the presets in `--list-charsets` order,
+/// led by `start` when it is an expression rather than a preset.
+fn charset_cycle(start: &str) -> Vec<String> {
+    let presets = all_charset_presets();
+    let mut list: Vec<String> = presets.iter().map(|p| p.to_string()).collect();
+    if !presets.contains(&start) {
+        list.insert(0, start.to_string());
+    }
+    list
+}
+
+/// Switches the rain to charset expression `spec`, keeping its weights and
+/// the user's custom characters. Wide glyphs turn on full width, which
+/// restarts the rain on every other column.
+fn switch_charset(cloud: &mut Cloud, opts: &RunOptions, spec: &str) {
+    let Ok(chars) = weighted_chars_from_str(spec, &opts.user_ranges, opts.def_ascii) else {
+        return;
+    };
+    let full_width = opts.fullwidth || chars.iter().any(|&(c, _)| is_wide(c));
+    cloud.init_weighted_chars(chars);
+    if full_width != cloud.full_width() {
+        let (cols, lines) = cloud.size();
+        cloud.set_full_width(full_width);
+        cloud.reset(cols, lines);
+        if opts.density_auto {
+            cloud.set_droplet_density(effective_density(
+                opts.base_density,
+                cols,
+                lines,
+                full_width,
+                true,
+            ));
+        }
+    }
+    cloud.force_draw_everything();
+}
+
+/// Applies `f` to the color adjustments and rebuilds the palette.
+fn adjust_colors(cloud: &mut Cloud, f: impl FnOnce(&mut ColorAdjust)) {
+    let mut adjust = cloud.color_adjust();
+    f(&mut adjust);
+    cloud.set_color_adjust(adjust);
+}
+
+/// Runs until the user quits, `end_time` passes or `cloud.raining` is
+/// cleared, drawing every changed frame to `term`.
+pub fn run_loop<B: Backend>(
+    term: &mut B,
+    cloud: &mut Cloud,
+    opts: &mut RunOptions,
+) -> std::io::Result<PerfStats> {
+    let (w, h) = term.size()?;
+    let mut frame = Frame::new(w, h, cloud.palette.bg);
+    let mut stats = PerfStats::default();
+
+    let target_period = opts.target_period;
+    let pause_period = Duration::from_millis(250);
+    let mut next_frame = Instant::now();
+    let mut perf_pressure: f32 = 0.0;
+    let charsets = charset_cycle(&opts.charset);
+    let mut charset_idx = charsets
+        .iter()
+        .position(|c| *c == opts.charset)
+        .unwrap_or(0);
+    let mut cycle_timer = opts
+        .color_cycle
+        .as_ref()
+        .map(|c| CycleTimer::new(c, Instant::now()));
+
+    while cloud.raining {
+        let frame_period = if cloud.pause {
+            pause_period
+        } else {
+            target_period
+        };
+        let frame_period_s = frame_period.as_secs_f32().max(0.000_001);
+
+        if opts.end_time.is_some_and(|end| Instant::now() >= end) {
+            cloud.raining = false;
+            break;
+        }
+        let mut pending_resize: Option<(u16, u16)> = None;
+
+        if opts
+            .term_reinit
+            .as_ref()
+            .is_some_and(|r| r.swap(false, Ordering::SeqCst))
+        {
+            term.resume()?;
+            let (nw, nh) = term.size()?;
+            pending_resize = Some((nw, nh));
+            cloud.force_draw_everything();
+            next_frame = Instant::now();
+        }
+
+        loop {
+            while term.poll_event(Duration::from_millis(0))? {
+                let ev = term.read_event()?;
+                match ev {
+                    Event::Resize(nw, nh) => {
+                        pending_resize = Some((nw, nh));
+                    }
+                    Event::Key(k) if k.kind == KeyEventKind::Press => {
+                        if opts.screensaver {
+                            cloud.raining = false;
+                            break;
+                        }
+
+                        match (k.code, k.modifiers) {
+                            (KeyCode::Esc, _) => cloud.raining = false,
+                            (KeyCode::Char('q'), _) => cloud.raining = false,
+                            (KeyCode::Char('z'), KeyModifiers::CONTROL) => {
+                                #[cfg(unix)]
+                                if let Some(reinit) = &opts.term_reinit {
+                                    term.restore()?;
+                                    reinit.store(true, Ordering::SeqCst);
+                                    let _ = low_level::raise(SIGSTOP);
+                                }
+                            }
+                            (KeyCode::Char(' '), _) => {
+                                cloud.reset(frame.width, frame.height);
+                                cloud.force_draw_everything();
+                            }
+                            (KeyCode::Char(key @ ('c' | 'C')), _) => {
+                                let dir = if key == 'c' { 1 } else { -1 };
+                                let next = cycle_color_scheme_in(
+                                    &opts.color_schemes,
+                                    cloud.color_scheme(),
+                                    dir,
+                                );
+                                cloud.set_color_scheme(next);
+                                if let (Some(cycle), Some(timer)) =
+                                    (&opts.color_cycle, cycle_timer.as_mut())
+                                {
+                                    timer.restart(cycle, Instant::now());
+                                }
+                            }
+                            (KeyCode::Char('s'), _) => {
+                                charset_idx = (charset_idx + 1) % charsets.len();
+                                switch_charset(cloud, opts, &charsets[charset_idx]);
+                            }
+                            (KeyCode::Char('S'), _) => {
+                                charset_idx = (charset_idx + charsets.len() - 1) % charsets.len();
+                                switch_charset(cloud, opts, &charsets[charset_idx]);
+                            }
+                            (KeyCode::Char('a'), _) => {
+                                cloud.set_async(!cloud.async_mode);
+                            }
+                            (KeyCode::Char('g'), _) => {
+                                cloud.set_glitchy(!cloud.glitchy);
+                            }
+                            (KeyCode::Char('p'), _) => {
+                                cloud.toggle_pause();
+                            }
+                            (KeyCode::Up, _) => {
+                                let mut cps = cloud.chars_per_sec;
+                                if cps <= 0.5 {
+                                    cps *= 2.0;
+                                } else {
+                                    cps += 1.0;
+                                }
+                                cloud.set_chars_per_sec(cps.min(1000.0));
+                            }
+                            (KeyCode::Down, _) => {
+                                let mut cps = cloud.chars_per_sec;
+                                if cps <= 1.0 {
+                                    cps /= 2.0;
+                                } else {
+                                    cps -= 1.0;
+                                }
+                                cloud.set_chars_per_sec(cps.max(0.001));
+                            }
+                            (KeyCode::Left, _) => {
+                                if cloud.glitchy {
+                                    let gp = (cloud.glitch_pct - 0.05).max(0.0);
+                                    cloud.set_glitch_pct(gp);
+                                }
+                            }
+                            (KeyCode::Right, _) => {
+                                if cloud.glitchy {
+                                    let gp = (cloud.glitch_pct + 0.05).min(1.0);
+                                    cloud.set_glitch_pct(gp);
+                                }
+                            }
+                            (KeyCode::Tab, _) => {
+                                cloud.set_shading_mode(cycle_shading_mode(cloud.shading_mode()));
+                            }
+                            (KeyCode::Char('b'), _) => {
+                                adjust_colors(cloud, |a| a.brightness += 0.1)
+                            }
+                            (KeyCode::Char('B'), _) => {
+                                adjust_colors(cloud, |a| a.brightness -= 0.1)
+                            }
+                            (KeyCode::Char('k'), _) => adjust_colors(cloud, |a| a.contrast += 0.1),
+                            (KeyCode::Char('K'), _) => adjust_colors(cloud, |a| a.contrast -= 0.1),
+                            (KeyCode::Char('v'), _) => {
+                                adjust_colors(cloud, |a| a.saturation += 0.1)
+                            }
+                            (KeyCode::Char('V'), _) => {
+                                adjust_colors(cloud, |a| a.saturation -= 0.1)
+                            }
+                            (KeyCode::Char('h'), _) => {
+                                adjust_colors(cloud, |a| a.hue_shift += 15.0)
+                            }
+                            (KeyCode::Char('H'), _) => {
+                                adjust_colors(cloud, |a| a.hue_shift -= 15.0)
+                            }
+                            (KeyCode::Char('r'), _) => adjust_colors(cloud, |a| {
+                                *a = ColorAdjust {
+                                    simulate_cvd: a.simulate_cvd,
+                                    ..ColorAdjust::default()
+                                }
+                            }),
+                            (KeyCode::Char('-'), _)
+                            | (KeyCode::Char('['), _)
+                            | (KeyCode::Char('_'), _) => {
+                                let d = (cloud.droplet_density - 0.25).max(0.01);
+                                cloud.set_droplet_density(d);
+                            }
+                            (KeyCode::Char('+'), _)
+                            | (KeyCode::Char('='), KeyModifiers::SHIFT)
+                            | (KeyCode::Char(']'), _) => {
+                                let d = (cloud.droplet_density + 0.25).min(5.0);
+                                cloud.set_droplet_density(d);
+                            }
+                            (KeyCode::Char('1'), _) => cloud.set_color_scheme(ColorScheme::Green),
+                            (KeyCode::Char('2'), _) => cloud.set_color_scheme(ColorScheme::Green2),
+                            (KeyCode::Char('3'), _) => cloud.set_color_scheme(ColorScheme::Green3),
+                            (KeyCode::Char('4'), _) => cloud.set_color_scheme(ColorScheme::Gold),
+                            (KeyCode::Char('5'), _) => cloud.set_color_scheme(ColorScheme::Neon),
+                            (KeyCode::Char('6'), _) => cloud.set_color_scheme(ColorScheme::Red),
+                            (KeyCode::Char('7'), _) => cloud.set_color_scheme(ColorScheme::Blue),
+                            (KeyCode::Char('8'), _) => cloud.set_color_scheme(ColorScheme::Cyan),
+                            (KeyCode::Char('9'), _) => cloud.set_color_scheme(ColorScheme::Purple),
+                            (KeyCode::Char('0'), _) => cloud.set_color_scheme(ColorScheme::Gray),
+                            (KeyCode::Char('!'), _) => cloud.set_color_scheme(ColorScheme::Rainbow),
+                            (KeyCode::Char('@'), _) => cloud.set_color_scheme(ColorScheme::Yellow),
+                            (KeyCode::Char('#'), _) => cloud.set_color_scheme(ColorScheme::Orange),
+                            (KeyCode::Char('$'), _) => cloud.set_color_scheme(ColorScheme::Fire),
+                            (KeyCode::Char('%'), _) => {
+                                cloud.set_color_scheme(ColorScheme::Vaporwave)
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !cloud.raining || pending_resize.is_some() {
+                break;
+            }
+
+            let now = Instant::now();
+            if now >= next_frame {
+                break;
+            }
+
+            let mut timeout = next_frame - now;
+            if let Some(end) = opts.end_time {
+                if now >= end {
+                    break;
+                }
+                timeout = timeout.min(end - now);
+            }
+            let _ = term.poll_event(timeout)?;
+        }
+
+        if !cloud.raining {
+            break;
+        }
+
+        if let Some((nw, nh)) = pending_resize {
+            cloud.reset(nw, nh);
+            frame = Frame::new(nw, nh, cloud.palette.bg);
+            if opts.density_auto {
+                cloud.set_droplet_density(effective_density(
+                    opts.base_density,
+                    nw,
+                    nh,
+                    cloud.full_width(),
+                    true,
+                ));
+            }
+            cloud.force_draw_everything();
+        }
+
+        if let (Some(cycle), Some(timer)) = (&opts.color_cycle, cycle_timer.as_mut()) {
+            let now = Instant::now();
+            if let Some((next, fade)) = timer.poll(cycle, cloud.color_scheme(), cloud.pause, now) {
+                cloud.fade_to_color_scheme(next, fade, now);
+            }
+        }
+
+        cloud.set_perf_pressure(perf_pressure);
+        let sim_base_s = frame_period.as_secs_f64() * 3.0;
+        let sim_factor = (1.0 - (perf_pressure as f64) * 0.7).clamp(0.3, 1.0);
+        let sim_min_s = (frame_period.as_secs_f64() * 0.5).max(0.001);
+        let sim_max_s = sim_base_s.min(0.5);
+        let sim_cap_s = (sim_base_s * sim_factor).clamp(sim_min_s, sim_max_s);
+        cloud.set_max_sim_delta(Duration::from_secs_f64(sim_cap_s));
+
+        let work_start = Instant::now();
+        cloud.rain(&mut frame);
+        let did_draw = frame.is_dirty_all() || !frame.dirty_indices().is_empty();
+        if did_draw {
+            term.draw(&mut frame)?;
+        }
+        let work_s = work_start.elapsed().as_secs_f32();
+        let overshoot = ((work_s / frame_period_s) - 1.0).clamp(0.0, 2.0);
+        if overshoot > 0.0 {
+            perf_pressure = (perf_pressure + (overshoot * 0.25)).min(1.0);
+        } else {
+            perf_pressure = (perf_pressure - 0.02).max(0.0);
+        }
+
+        stats.frames = stats.frames.saturating_add(1);
+        if did_draw {
+            stats.drawn_frames = stats.drawn_frames.saturating_add(1);
+        }
+        stats.work_sum_s += work_s as f64;
+        stats.work_max_s = stats.work_max_s.max(work_s);
+        stats.pressure_sum += perf_pressure as f64;
+        stats.pressure_max = stats.pressure_max.max(perf_pressure);
+        if overshoot > 0.0 {
+            stats.overshoot_frames = stats.overshoot_frames.saturating_add(1);
+        }
+
+        let now = Instant::now();
+        next_frame = next_frame.checked_add(frame_period).unwrap_or(now);
+        if now > next_frame {
+            next_frame = now.checked_add(frame_period).unwrap_or(now);
+        }
+    }
+
+    Ok(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::runtime::all_color_schemes;
+    use crate::terminal::MemoryBackend;
+    use crate::BoldMode;
+    use crossterm::event::KeyEvent;
+
+    #[test]
+    fn loop_runs_against_memory_backend() {
+        let mut term = MemoryBackend::new(20, 8);
+        let mut cloud = Cloud::builder().bold_mode(BoldMode::Off).seed(7).build();
+        cloud.init_chars(vec!['0', '1']);
+        cloud.reset(20, 8);
+
+        term.push_event(Event::Key(KeyEvent::from(KeyCode::Char('c'))));
+        term.resize(24, 6);
+        term.push_event(Event::Key(KeyEvent::from(KeyCode::Char('q'))));
+
+        let mut opts = RunOptions {
+            target_period: Duration::from_millis(1),
+            end_time: Some(Instant::now() + Duration::from_secs(5)),
+            screensaver: false,
+            fullwidth: false,
+            base_density: 1.0,
+            density_auto: false,
+            charset: "binary".to_string(),
+            user_ranges: Vec::new(),
+            def_ascii: true,
+            color_schemes: all_color_schemes().to_vec(),
+            color_cycle: None,
+            term_reinit: None,
+        };
+        run_loop(&mut term, &mut cloud, &mut opts).unwrap();
+
+        assert!(!cloud.raining);
+        assert_eq!(cloud.color_scheme(), &ColorScheme::Green2);
+        assert_eq!(term.size().unwrap(), (24, 6));
+        assert!(term.cell(23, 5).is_some());
+        assert!(term.cell(24, 0).is_none());
+    }
+
+    #[test]
+    fn charset_cycle_keeps_the_starting_expression() {
+        let start = "katakana:3,digits:1";
+        let list = charset_cycle(start);
+        assert_eq!(list[0], start);
+        assert_eq!(list.len(), all_charset_presets().len() + 1);
+        assert_eq!(charset_cycle("greek").len(), all_charset_presets().len());
+
+        let mut cloud = Cloud::builder().seed(1).build();
+        cloud.reset(20, 8);
+        let opts = RunOptions {
+            target_period: Duration::from_millis(1),
+            end_time: None,
+            screensaver: false,
+            fullwidth: false,
+            base_density: 1.0,
+            density_auto: false,
+            charset: start.to_string(),
+            user_ranges: vec![('@', '@')],
+            def_ascii: true,
+            color_schemes: all_color_schemes().to_vec(),
+            color_cycle: None,
+            term_reinit: None,
+        };
+        switch_charset(&mut cloud, &opts, "binary");
+        assert!(cloud.chars.contains(&'@'));
+        assert!(!cloud.chars.contains(&'\u{ff71}'));
+        switch_charset(&mut cloud, &opts, start);
+        assert!(cloud.chars.contains(&'\u{ff71}'));
+        assert!(cloud.chars.contains(&'@'));
+    }
+
+    #[test]
+    fn switching_to_a_wide_charset_turns_on_full_width() {
+        let mut cloud = Cloud::builder().seed(1).build();
+        cloud.reset(20, 8);
+        let mut opts = RunOptions {
+            target_period: Duration::from_millis(1),
+            end_time: None,
+            screensaver: false,
+            fullwidth: false,
+            base_density: 1.0,
+            density_auto: true,
+            charset: "binary".to_string(),
+            user_ranges: Vec::new(),
+            def_ascii: true,
+            color_schemes: all_color_schemes().to_vec(),
+            color_cycle: None,
+            term_reinit: None,
+        };
+        switch_charset(&mut cloud, &opts, "script:hiragana");
+        assert!(cloud.full_width());
+        assert_eq!(cloud.size(), (20, 8));
+        assert_eq!(
+            cloud.droplet_density(),
+            effective_density(1.0, 20, 8, true, true)
+        );
+        switch_charset(&mut cloud, &opts, "binary");
+        assert!(!cloud.full_width());
+
+        opts.fullwidth = true;
+        switch_charset(&mut cloud, &opts, "binary");
+        assert!(cloud.full_width());
+    }
+
+    #[test]
+    fn color_cycle_wraps_pauses_and_clamps_the_crossfade() {
+        let cycle = ColorCycle {
+            interval: Duration::from_secs(10),
+            themes: vec![ColorScheme::Fire, ColorScheme::Ocean],
+            crossfade: Duration::from_secs(30),
+        };
+        assert_eq!(
+            cycle.next_after(&ColorScheme::Fire),
+            Some(ColorScheme::Ocean)
+        );
+        assert_eq!(
+            cycle.next_after(&ColorScheme::Ocean),
+            Some(ColorScheme::Fire)
+        );
+        assert_eq!(
+            cycle.next_after(&ColorScheme::Green),
+            Some(ColorScheme::Fire)
+        );
+
+        let start = Instant::now();
+        let at = |s| start + Duration::from_secs(s);
+        let mut timer = CycleTimer::new(&cycle, start);
+        let current = ColorScheme::Ocean;
+        assert_eq!(timer.poll(&cycle, &current, false, at(6)), None);
+        // Twenty paused seconds do not count toward the interval.
+        assert_eq!(timer.poll(&cycle, &current, true, at(26)), None);
+        assert_eq!(timer.poll(&cycle, &current, false, at(29)), None);
+        assert_eq!(
+            timer.poll(&cycle, &current, false, at(30)),
+            Some((ColorScheme::Fire, Duration::from_secs(10)))
+        );
+        assert_eq!(timer.poll(&cycle, &current, false, at(39)), None);
+        assert!(timer.poll(&cycle, &current, false, at(40)).is_some());
+
+        let short = ColorCycle {
+            crossfade: Duration::from_secs(2),
+            ..cycle
+        };
+        assert_eq!(short.fade(), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn picking_a_theme_by_hand_restarts_the_color_cycle() {
+        let cycle = ColorCycle {
+            interval: Duration::from_secs(10),
+            themes: vec![ColorScheme::Fire, ColorScheme::Ocean],
+            crossfade: Duration::from_secs(1),
+        };
+        let start = Instant::now();
+        let at = |s| start + Duration::from_secs(s);
+        let mut timer = CycleTimer::new(&cycle, start);
+        let current = ColorScheme::Fire;
+        assert_eq!(timer.poll(&cycle, &current, false, at(8)), None);
+
+        // A `c` press at 8s pushes the next change from 10s to 18s.
+        timer.restart(&cycle, at(8));
+        assert_eq!(timer.poll(&cycle, &current, false, at(10)), None);
+        assert_eq!(timer.poll(&cycle, &current, false, at(17)), None);
+        assert_eq!(
+            timer.poll(&cycle, &current, false, at(18)),
+            Some((ColorScheme::Ocean, Duration::from_secs(1)))
+        );
+    }
+}
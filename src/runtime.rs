@@ -1,20 +1,73 @@
 // Copyright (c) 2026 rezky_nightky
 
+//! Rendering modes and theme identifiers.
+
+use std::sync::Arc;
+
+use crate::theme::CustomTheme;
+
+/// How many colors the output terminal supports.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ColorMode {
     Mono,
-    #[allow(dead_code)]
     Color16,
     Color256,
     TrueColor,
 }
 
+/// How a droplet's body picks colors from the palette.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ShadingMode {
+    /// A fixed random palette color per cell.
     Random,
+    /// Brightest at the head, fading toward the tail.
     DistanceFromHead,
+    /// Fades from the head like `DistanceFromHead` with the hue turned by
+    /// column, once round the color wheel from the left edge to the right.
+    Column,
+    /// A gradient from the top row (tail color) to the bottom (head color).
+    Row,
+    /// Fades from the head like `DistanceFromHead` while the hue of the whole
+    /// rain turns round the color wheel over time.
+    Time,
+    /// Droplets start out bright and dim as they age.
+    Age,
+}
+
+/// Shading modes in `--shadingmode` number order, which Tab cycles through.
+pub const SHADING_MODES: [ShadingMode; 6] = [
+    ShadingMode::Random,
+    ShadingMode::DistanceFromHead,
+    ShadingMode::Column,
+    ShadingMode::Row,
+    ShadingMode::Time,
+    ShadingMode::Age,
+];
+
+/// The shading mode after `current` in [`SHADING_MODES`], wrapping around.
+pub fn cycle_shading_mode(current: ShadingMode) -> ShadingMode {
+    let pos = SHADING_MODES
+        .iter()
+        .position(|&m| m == current)
+        .unwrap_or(0);
+    SHADING_MODES[(pos + 1) % SHADING_MODES.len()]
+}
+
+/// Continuous truecolor shading for the gradient [`ShadingMode`]s (all but
+/// `Random`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmoothShading {
+    /// One palette color per cell.
+    Off,
+    /// Colors blended between palette entries along the droplet's length.
+    Length,
+    /// As `Length`, and the gradient also glides with the head between
+    /// whole-cell steps.
+    Time,
 }
 
+/// Which glyphs are drawn bold.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BoldMode {
     Off,
@@ -22,7 +75,9 @@ pub enum BoldMode {
     All,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Built-in color themes (see `--list-colors`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ColorScheme {
     Green,
     Green2,
@@ -67,4 +122,250 @@ pub enum ColorScheme {
     Meteor,
     Eclipse,
     DeepSpace,
+    /// Colorblind-safe ramps: lightness rises steadily and hues run along
+    /// the blue-yellow axis that all common color-vision deficiencies keep.
+    Viridis,
+    Cividis,
+    Magma,
+    /// A user-defined theme from the theme file.
+    Custom(Arc<CustomTheme>),
+    /// Shades of a single color (`--color '#RRGGBB'` or `rgb(r,g,b)`).
+    Rgb(u8, u8, u8),
+    /// Gradients of several themes split along one ramp (`fire+ocean`).
+    Blend(Arc<[ColorScheme]>),
+    /// Each column colored by one of several themes (`mix:neon,green,gold`).
+    Mix(Arc<[ColorScheme]>),
+}
+
+/// Most themes a `+` or `mix:` theme can combine.
+pub const MAX_COMPOUND_PARTS: usize = 16;
+
+/// Built-in themes in `--list-colors` order.
+const BUILTIN_COLOR_SCHEMES: &[ColorScheme] = &[
+    ColorScheme::Green,
+    ColorScheme::Green2,
+    ColorScheme::Green3,
+    ColorScheme::Yellow,
+    ColorScheme::Orange,
+    ColorScheme::Red,
+    ColorScheme::Blue,
+    ColorScheme::Cyan,
+    ColorScheme::Gold,
+    ColorScheme::Rainbow,
+    ColorScheme::Purple,
+    ColorScheme::Neon,
+    ColorScheme::Fire,
+    ColorScheme::Ocean,
+    ColorScheme::Forest,
+    ColorScheme::Vaporwave,
+    ColorScheme::Gray,
+    ColorScheme::Snow,
+    ColorScheme::Aurora,
+    ColorScheme::FancyDiamond,
+    ColorScheme::Cosmos,
+    ColorScheme::Nebula,
+    ColorScheme::Spectrum20,
+    ColorScheme::Stars,
+    ColorScheme::Mars,
+    ColorScheme::Venus,
+    ColorScheme::Mercury,
+    ColorScheme::Jupiter,
+    ColorScheme::Saturn,
+    ColorScheme::Uranus,
+    ColorScheme::Neptune,
+    ColorScheme::Pluto,
+    ColorScheme::Moon,
+    ColorScheme::Sun,
+    ColorScheme::Comet,
+    ColorScheme::Galaxy,
+    ColorScheme::Supernova,
+    ColorScheme::BlackHole,
+    ColorScheme::Andromeda,
+    ColorScheme::Stardust,
+    ColorScheme::Meteor,
+    ColorScheme::Eclipse,
+    ColorScheme::DeepSpace,
+    ColorScheme::Viridis,
+    ColorScheme::Cividis,
+    ColorScheme::Magma,
+];
+
+/// Built-in themes in `--list-colors` order.
+pub fn all_color_schemes() -> &'static [ColorScheme] {
+    BUILTIN_COLOR_SCHEMES
+}
+
+/// Steps `dir` places through [`all_color_schemes`], wrapping around.
+pub fn cycle_color_scheme(current: &ColorScheme, dir: i32) -> ColorScheme {
+    cycle_color_scheme_in(all_color_schemes(), current, dir)
+}
+
+/// Steps `dir` places through `list`, wrapping around; `Green` if
+/// `current` is not in it.
+pub fn cycle_color_scheme_in(list: &[ColorScheme], current: &ColorScheme, dir: i32) -> ColorScheme {
+    let Some(pos) = list.iter().position(|c| c == current) else {
+        return ColorScheme::Green;
+    };
+
+    let n = list.len() as i32;
+    let mut idx = pos as i32 + dir;
+    idx = ((idx % n) + n) % n;
+    list[idx as usize].clone()
+}
+
+/// Parses a theme name or alias as accepted by `--color`, including
+/// `#RRGGBB`/`rgb(r,g,b)` quick themes and `a+b`/`mix:a,b` compound themes.
+pub fn parse_color_scheme(s: &str) -> Result<ColorScheme, String> {
+    parse_color_scheme_with(s, &[])
+}
+
+/// As [`parse_color_scheme`], also accepting the names of `custom` themes.
+pub fn parse_color_scheme_with(
+    s: &str,
+    custom: &[Arc<CustomTheme>],
+) -> Result<ColorScheme, String> {
+    let name = s.trim().to_ascii_lowercase();
+    if let Some(c) = parse_builtin_color_scheme(&name) {
+        return Ok(c);
+    }
+    if let Some(list) = name.strip_prefix("mix:") {
+        return parse_compound(
+            s,
+            &split_outside_parens(list, ','),
+            custom,
+            ColorScheme::Mix,
+        );
+    }
+    if name.contains('+') {
+        return parse_compound(
+            s,
+            &name.split('+').collect::<Vec<_>>(),
+            custom,
+            ColorScheme::Blend,
+        );
+    }
+    if let Some(rgb) = parse_rgb_color(&name) {
+        let (r, g, b) = rgb.map_err(|e| format!("invalid color: {} ({})", s, e))?;
+        return Ok(ColorScheme::Rgb(r, g, b));
+    }
+    custom
+        .iter()
+        .find(|t| t.name.to_ascii_lowercase() == name)
+        .map(|t| ColorScheme::Custom(Arc::clone(t)))
+        .ok_or_else(|| format!("invalid color: {} (see --list-colors)", s))
+}
+
+/// `s` split at `sep`, except inside `rgb(...)`.
+fn split_outside_parens(s: &str, sep: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == sep && depth <= 0 => {
+                parts.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// A `+` or `mix:` theme made of the themes named in `parts`.
+fn parse_compound(
+    s: &str,
+    parts: &[&str],
+    custom: &[Arc<CustomTheme>],
+    make: fn(Arc<[ColorScheme]>) -> ColorScheme,
+) -> Result<ColorScheme, String> {
+    if parts.len() < 2 || parts.len() > MAX_COMPOUND_PARTS {
+        return Err(format!(
+            "invalid color: {} (combine 2 to {} themes)",
+            s, MAX_COMPOUND_PARTS
+        ));
+    }
+    let mut schemes = Vec::with_capacity(parts.len());
+    for part in parts {
+        let c = parse_color_scheme_with(part, custom)?;
+        if matches!(c, ColorScheme::Blend(_) | ColorScheme::Mix(_)) {
+            return Err(format!(
+                "invalid color: {} (`+` and `mix:` themes cannot be nested)",
+                s
+            ));
+        }
+        schemes.push(c);
+    }
+    Ok(make(schemes.into()))
+}
+
+/// `#RRGGBB` or `rgb(r,g,b)`; `None` if `s` looks like neither.
+fn parse_rgb_color(s: &str) -> Option<Result<(u8, u8, u8), String>> {
+    if s.starts_with('#') {
+        return Some(crate::theme::parse_hex_rgb(s).map_err(|_| "expected #RRGGBB".to_string()));
+    }
+    let inner = s.strip_prefix("rgb(")?.strip_suffix(')')?;
+    let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+    let channels: Option<Vec<u8>> = parts.iter().map(|p| p.parse::<u8>().ok()).collect();
+    Some(match channels.as_deref() {
+        Some(&[r, g, b]) => Ok((r, g, b)),
+        _ => Err("expected rgb(r,g,b) with values 0-255".to_string()),
+    })
+}
+
+/// Built-in theme for a lowercase name or alias.
+pub(crate) fn parse_builtin_color_scheme(name: &str) -> Option<ColorScheme> {
+    match name {
+        "green" => Some(ColorScheme::Green),
+        "green2" => Some(ColorScheme::Green2),
+        "green3" => Some(ColorScheme::Green3),
+        "yellow" => Some(ColorScheme::Yellow),
+        "orange" => Some(ColorScheme::Orange),
+        "red" => Some(ColorScheme::Red),
+        "blue" => Some(ColorScheme::Blue),
+        "cyan" => Some(ColorScheme::Cyan),
+        "gold" => Some(ColorScheme::Gold),
+        "rainbow" => Some(ColorScheme::Rainbow),
+        "purple" => Some(ColorScheme::Purple),
+        "neon" | "synthwave" => Some(ColorScheme::Neon),
+        "fire" | "inferno" => Some(ColorScheme::Fire),
+        "ocean" | "deep-sea" | "deep_sea" | "deepsea" => Some(ColorScheme::Ocean),
+        "forest" | "jungle" => Some(ColorScheme::Forest),
+        "vaporwave" => Some(ColorScheme::Vaporwave),
+        "gray" | "grey" => Some(ColorScheme::Gray),
+        "snow" => Some(ColorScheme::Snow),
+        "aurora" => Some(ColorScheme::Aurora),
+        "fancy-diamond" | "fancy_diamond" | "fancydiamond" => Some(ColorScheme::FancyDiamond),
+        "cosmos" => Some(ColorScheme::Cosmos),
+        "nebula" => Some(ColorScheme::Nebula),
+        "spectrum20" | "spectrum-20" | "spectrum_20" | "theme20" | "theme-20" | "theme_20" => {
+            Some(ColorScheme::Spectrum20)
+        }
+        "stars" | "star" => Some(ColorScheme::Stars),
+        "mars" => Some(ColorScheme::Mars),
+        "venus" => Some(ColorScheme::Venus),
+        "mercury" => Some(ColorScheme::Mercury),
+        "jupiter" => Some(ColorScheme::Jupiter),
+        "saturn" => Some(ColorScheme::Saturn),
+        "uranus" => Some(ColorScheme::Uranus),
+        "neptune" => Some(ColorScheme::Neptune),
+        "pluto" => Some(ColorScheme::Pluto),
+        "moon" => Some(ColorScheme::Moon),
+        "sun" => Some(ColorScheme::Sun),
+        "comet" => Some(ColorScheme::Comet),
+        "galaxy" => Some(ColorScheme::Galaxy),
+        "supernova" | "super-nova" | "super_nova" => Some(ColorScheme::Supernova),
+        "blackhole" | "black-hole" | "black_hole" => Some(ColorScheme::BlackHole),
+        "andromeda" => Some(ColorScheme::Andromeda),
+        "stardust" | "star-dust" | "star_dust" => Some(ColorScheme::Stardust),
+        "meteor" => Some(ColorScheme::Meteor),
+        "eclipse" => Some(ColorScheme::Eclipse),
+        "deepspace" | "deep-space" | "deep_space" => Some(ColorScheme::DeepSpace),
+        "viridis" => Some(ColorScheme::Viridis),
+        "cividis" => Some(ColorScheme::Cividis),
+        "magma" => Some(ColorScheme::Magma),
+        _ => None,
+    }
 }
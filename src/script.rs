@@ -0,0 +1,294 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Unicode scripts for `--charset script:NAME`.
+//!
+//! Each script lists the standalone glyphs of its main block: letters and
+//! digits, without combining marks, which cannot stand alone in a cell.
+
+/// A writing system and the code point ranges used for its rain.
+#[derive(Debug)]
+pub struct Script {
+    /// Unicode script name, matched case-insensitively.
+    pub name: &'static str,
+    /// Inclusive code point ranges.
+    pub ranges: &'static [(u32, u32)],
+    /// Glyphs take two terminal cells; use `--fullwidth`.
+    pub wide: bool,
+}
+
+impl Script {
+    /// The script's glyphs, in code point order.
+    pub fn chars(&self) -> Vec<char> {
+        self.ranges
+            .iter()
+            .flat_map(|&(a, b)| (a..=b).filter_map(char::from_u32))
+            .collect()
+    }
+}
+
+const fn script(name: &'static str, ranges: &'static [(u32, u32)]) -> Script {
+    Script {
+        name,
+        ranges,
+        wide: false,
+    }
+}
+
+const fn wide_script(name: &'static str, ranges: &'static [(u32, u32)]) -> Script {
+    Script {
+        name,
+        ranges,
+        wide: true,
+    }
+}
+
+/// Scripts in `--list-scripts` order.
+pub const SCRIPTS: &[Script] = &[
+    script(
+        "Latin",
+        &[
+            (0x41, 0x5A),
+            (0x61, 0x7A),
+            (0xC0, 0xD6),
+            (0xD8, 0xF6),
+            (0xF8, 0x24F),
+        ],
+    ),
+    script("Greek", &[(0x391, 0x3A1), (0x3A3, 0x3A9), (0x3B1, 0x3C9)]),
+    script("Cyrillic", &[(0x400, 0x45F)]),
+    script("Armenian", &[(0x531, 0x556), (0x561, 0x586)]),
+    script("Hebrew", &[(0x5D0, 0x5EA)]),
+    script("Arabic", &[(0x621, 0x63A), (0x641, 0x64A), (0x660, 0x669)]),
+    script("Syriac", &[(0x710, 0x72F)]),
+    script("Thaana", &[(0x780, 0x7A5)]),
+    script("Nko", &[(0x7C0, 0x7EA)]),
+    script(
+        "Devanagari",
+        &[
+            (0x904, 0x939),
+            (0x958, 0x961),
+            (0x966, 0x96F),
+            (0x972, 0x97F),
+        ],
+    ),
+    script(
+        "Bengali",
+        &[
+            (0x985, 0x98C),
+            (0x98F, 0x990),
+            (0x993, 0x9A8),
+            (0x9AA, 0x9B0),
+            (0x9B2, 0x9B2),
+            (0x9B6, 0x9B9),
+            (0x9E6, 0x9EF),
+        ],
+    ),
+    script(
+        "Gurmukhi",
+        &[
+            (0xA05, 0xA0A),
+            (0xA0F, 0xA10),
+            (0xA13, 0xA28),
+            (0xA2A, 0xA30),
+            (0xA66, 0xA6F),
+        ],
+    ),
+    script(
+        "Gujarati",
+        &[
+            (0xA85, 0xA8D),
+            (0xA8F, 0xA91),
+            (0xA93, 0xAA8),
+            (0xAAA, 0xAB0),
+            (0xAE6, 0xAEF),
+        ],
+    ),
+    script(
+        "Tamil",
+        &[
+            (0xB85, 0xB8A),
+            (0xB8E, 0xB90),
+            (0xB92, 0xB95),
+            (0xB99, 0xB9A),
+            (0xB9C, 0xB9C),
+            (0xB9E, 0xB9F),
+            (0xBA3, 0xBA4),
+            (0xBA8, 0xBAA),
+            (0xBAE, 0xBB9),
+            (0xBE6, 0xBEF),
+        ],
+    ),
+    script(
+        "Telugu",
+        &[
+            (0xC05, 0xC0C),
+            (0xC0E, 0xC10),
+            (0xC12, 0xC28),
+            (0xC2A, 0xC39),
+            (0xC66, 0xC6F),
+        ],
+    ),
+    script(
+        "Kannada",
+        &[
+            (0xC85, 0xC8C),
+            (0xC8E, 0xC90),
+            (0xC92, 0xCA8),
+            (0xCAA, 0xCB3),
+            (0xCB5, 0xCB9),
+            (0xCE6, 0xCEF),
+        ],
+    ),
+    script(
+        "Malayalam",
+        &[
+            (0xD05, 0xD0C),
+            (0xD0E, 0xD10),
+            (0xD12, 0xD3A),
+            (0xD66, 0xD6F),
+        ],
+    ),
+    script(
+        "Sinhala",
+        &[
+            (0xD85, 0xD96),
+            (0xD9A, 0xDB1),
+            (0xDB3, 0xDBB),
+            (0xDBD, 0xDBD),
+            (0xDC0, 0xDC6),
+        ],
+    ),
+    script(
+        "Thai",
+        &[
+            (0xE01, 0xE30),
+            (0xE32, 0xE33),
+            (0xE40, 0xE46),
+            (0xE50, 0xE59),
+        ],
+    ),
+    script(
+        "Lao",
+        &[
+            (0xE81, 0xE82),
+            (0xE84, 0xE84),
+            (0xE86, 0xE8A),
+            (0xE8C, 0xEA3),
+            (0xEA5, 0xEA5),
+            (0xEA7, 0xEB0),
+            (0xEB2, 0xEB2),
+            (0xEBD, 0xEBD),
+            (0xEC0, 0xEC4),
+            (0xED0, 0xED9),
+        ],
+    ),
+    script("Tibetan", &[(0xF20, 0xF29), (0xF40, 0xF47), (0xF49, 0xF6C)]),
+    script("Myanmar", &[(0x1000, 0x102A), (0x1040, 0x1049)]),
+    script("Georgian", &[(0x10D0, 0x10FA)]),
+    script(
+        "Ethiopic",
+        &[
+            (0x1200, 0x1248),
+            (0x1250, 0x1256),
+            (0x1260, 0x1288),
+            (0x1290, 0x12B0),
+            (0x12B8, 0x12BE),
+            (0x12C8, 0x12D6),
+            (0x12D8, 0x1310),
+            (0x1318, 0x135A),
+        ],
+    ),
+    script("Cherokee", &[(0x13A0, 0x13F5)]),
+    script("Canadian_Aboriginal", &[(0x1401, 0x166C)]),
+    script("Ogham", &[(0x1681, 0x169A)]),
+    script("Runic", &[(0x16A0, 0x16EA)]),
+    script("Khmer", &[(0x1780, 0x17B3), (0x17E0, 0x17E9)]),
+    script("Mongolian", &[(0x1820, 0x1878)]),
+    script("Braille", &[(0x2800, 0x28FF)]),
+    script("Coptic", &[(0x2C80, 0x2CE4)]),
+    script("Tifinagh", &[(0x2D30, 0x2D67)]),
+    wide_script("Hiragana", &[(0x3041, 0x3096)]),
+    wide_script("Katakana", &[(0x30A1, 0x30FA)]),
+    wide_script("Bopomofo", &[(0x3105, 0x312F)]),
+    wide_script("Han", &[(0x4E00, 0x9FFF)]),
+    wide_script("Yi", &[(0xA000, 0xA48C)]),
+    script("Vai", &[(0xA500, 0xA62B)]),
+    wide_script("Hangul", &[(0xAC00, 0xD7A3)]),
+];
+
+/// Whether `c` belongs to one of the wide scripts and takes two cells.
+pub fn is_wide(c: char) -> bool {
+    let c = c as u32;
+    SCRIPTS
+        .iter()
+        .filter(|s| s.wide)
+        .any(|s| s.ranges.iter().any(|&(a, b)| (a..=b).contains(&c)))
+}
+
+/// Looks up a script by name, ignoring case.
+pub fn find_script(name: &str) -> Option<&'static Script> {
+    let name = name.trim();
+    SCRIPTS.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{Duration, Instant};
+
+    use crate::charset::weighted_chars_from_str;
+    use crate::cloud::Cloud;
+    use crate::frame::Frame;
+    use crate::runtime::{BoldMode, ShadingMode};
+
+    #[test]
+    fn scripts_resolve_to_printable_glyphs() {
+        for s in SCRIPTS {
+            let chars = s.chars();
+            assert!(!chars.is_empty(), "{}", s.name);
+            assert!(chars.windows(2).all(|w| w[0] < w[1]), "{}", s.name);
+            assert!(!chars.iter().any(|c| c.is_control()), "{}", s.name);
+        }
+        assert_eq!(find_script("hangul").unwrap().chars().len(), 11172);
+
+        let thai = weighted_chars_from_str("script:Thai:3,digits", &[], false).unwrap();
+        assert!(thai.iter().any(|&(c, _)| c == 'ก'));
+        assert!(thai.iter().any(|&(c, _)| c == '7'));
+        let err = weighted_chars_from_str("script:Klingon", &[], false).unwrap_err();
+        assert!(err.contains("--list-scripts"), "{}", err);
+    }
+
+    #[test]
+    fn wide_scripts_rain_on_every_other_column() {
+        let chars = weighted_chars_from_str("script:Hangul,digits", &[], false).unwrap();
+        assert!(chars.iter().any(|&(c, _)| is_wide(c)));
+        assert!(!is_wide('7') && !is_wide('ก'));
+
+        let full_width = chars.iter().any(|&(c, _)| is_wide(c));
+        let mut cloud = Cloud::builder()
+            .full_width(full_width)
+            .shading_mode(ShadingMode::Random)
+            .bold_mode(BoldMode::Off)
+            .seed(1)
+            .build();
+        cloud.init_weighted_chars(chars);
+        let start = Instant::now();
+        cloud.reset_at(40, 12, start);
+        let mut frame = Frame::new(40, 12, None);
+        for i in 0..120 {
+            cloud.rain_at(&mut frame, start + Duration::from_millis(i * 16));
+        }
+        let mut drawn = 0;
+        for y in 0..12 {
+            for x in 0..40 {
+                let ch = frame.get(x, y).unwrap().ch;
+                if is_wide(ch) {
+                    drawn += 1;
+                    assert_eq!(x % 2, 0, "{} at column {}", ch, x);
+                }
+            }
+        }
+        assert!(drawn > 0);
+    }
+}
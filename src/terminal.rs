@@ -1,15 +1,22 @@
 // Copyright (c) 2026 rezky_nightky
 
-use std::io::{stdout, Result, Stdout, Write};
+//! Rendering backends: the [`Backend`] trait, the crossterm [`Terminal`] and
+//! the in-memory [`MemoryBackend`], sharing one diffing frame encoder.
+
+use std::collections::VecDeque;
+use std::io::{stdout, Error, ErrorKind, Result, Stdout, Write};
+use std::time::Duration;
 
 use crossterm::{
-    cursor, event,
+    cursor,
+    event::{self, Event},
     style::{
         Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor,
     },
     terminal, ExecutableCommand, QueueableCommand,
 };
 
+use crate::cast::CastWriter;
 use crate::cell::Cell;
 use crate::frame::Frame;
 
@@ -38,40 +45,20 @@ impl LastFrame {
     }
 }
 
-pub struct Terminal {
-    stdout: Stdout,
+/// Diffing frame encoder shared by all backends: turns the dirty cells of a
+/// [`Frame`] into cursor moves, SGR changes and text, appended to `out`.
+struct Renderer {
+    out: Vec<u8>,
     last: Option<LastFrame>,
     run_buf: String,
     row_dirty: Vec<Vec<usize>>,
     touched_rows: Vec<u16>,
 }
 
-impl Terminal {
-    pub fn new() -> Result<Self> {
-        let mut out = stdout();
-        terminal::enable_raw_mode()?;
-        let init_res: Result<()> = (|| {
-            out.execute(terminal::EnterAlternateScreen)?;
-            out.execute(cursor::Hide)?;
-            let _ = out.execute(terminal::DisableLineWrap);
-            out.execute(SetAttribute(Attribute::Reset))?;
-            out.execute(ResetColor)?;
-            out.execute(terminal::Clear(terminal::ClearType::All))?;
-            out.flush()?;
-            Ok(())
-        })();
-        if let Err(e) = init_res {
-            let _ = out.execute(SetAttribute(Attribute::Reset));
-            let _ = out.execute(ResetColor);
-            let _ = out.execute(cursor::Show);
-            let _ = out.execute(terminal::EnableLineWrap);
-            let _ = out.execute(terminal::LeaveAlternateScreen);
-            let _ = terminal::disable_raw_mode();
-            let _ = out.flush();
-            return Err(e);
-        }
-        Ok(Self {
-            stdout: out,
+impl Renderer {
+    fn new() -> Self {
+        Self {
+            out: Vec::with_capacity(16 * 1024),
             last: None,
             run_buf: {
                 let mut s = String::new();
@@ -80,22 +67,17 @@ impl Terminal {
             },
             row_dirty: Vec::new(),
             touched_rows: Vec::new(),
-        })
-    }
-
-    pub fn size(&self) -> Result<(u16, u16)> {
-        terminal::size()
-    }
-
-    pub fn poll_event(timeout: std::time::Duration) -> Result<bool> {
-        event::poll(timeout)
+        }
     }
 
-    pub fn read_event() -> Result<event::Event> {
-        event::read()
+    /// Forgets what is on screen so the next draw repaints everything.
+    fn invalidate(&mut self) {
+        self.last = None;
     }
 
-    pub fn draw(&mut self, frame: &mut Frame) -> Result<()> {
+    /// Encodes the changed cells of `frame` into `out` and clears its dirty
+    /// state.
+    fn draw(&mut self, frame: &mut Frame) -> Result<()> {
         let mut cur_fg: Option<Color> = None;
         let mut cur_bg: Option<Color> = None;
         let mut cur_bold: bool = false;
@@ -108,8 +90,7 @@ impl Terminal {
             .unwrap_or(true);
 
         if needs_full_redraw {
-            self.stdout
-                .queue(terminal::Clear(terminal::ClearType::All))?;
+            self.out.queue(terminal::Clear(terminal::ClearType::All))?;
         }
 
         let can_reuse_last = !needs_full_redraw && self.last.is_some();
@@ -130,31 +111,31 @@ impl Terminal {
             let last = self.last.as_mut().expect("set above");
 
             for y in 0..frame.height {
-                self.stdout.queue(cursor::MoveTo(0, y))?;
+                self.out.queue(cursor::MoveTo(0, y))?;
                 for x in 0..frame.width {
                     let idx = y as usize * frame.width as usize + x as usize;
                     let cell = frame.cell_at_index(idx);
 
                     if cell.fg != cur_fg {
                         if let Some(fg) = cell.fg {
-                            self.stdout.queue(SetForegroundColor(fg))?;
+                            self.out.queue(SetForegroundColor(fg))?;
                         } else {
-                            self.stdout.queue(SetForegroundColor(Color::Reset))?;
+                            self.out.queue(SetForegroundColor(Color::Reset))?;
                         }
                         cur_fg = cell.fg;
                     }
 
                     if cell.bg != cur_bg {
                         if let Some(bg) = cell.bg {
-                            self.stdout.queue(SetBackgroundColor(bg))?;
+                            self.out.queue(SetBackgroundColor(bg))?;
                         } else {
-                            self.stdout.queue(SetBackgroundColor(Color::Reset))?;
+                            self.out.queue(SetBackgroundColor(Color::Reset))?;
                         }
                         cur_bg = cell.bg;
                     }
 
                     if cell.bold != cur_bold {
-                        self.stdout.queue(SetAttribute(if cell.bold {
+                        self.out.queue(SetAttribute(if cell.bold {
                             Attribute::Bold
                         } else {
                             Attribute::NormalIntensity
@@ -162,15 +143,14 @@ impl Terminal {
                         cur_bold = cell.bold;
                     }
 
-                    self.stdout.queue(Print(cell.ch))?;
+                    self.out.queue(Print(cell.ch))?;
 
                     last.cells[idx] = cell;
                 }
             }
 
-            self.stdout.queue(SetAttribute(Attribute::Reset))?;
-            self.stdout.queue(ResetColor)?;
-            self.stdout.flush()?;
+            self.out.queue(SetAttribute(Attribute::Reset))?;
+            self.out.queue(ResetColor)?;
 
             frame.clear_dirty();
             return Ok(());
@@ -251,29 +231,29 @@ impl Terminal {
                 }
 
                 if cur_pos != Some((x0, y0)) {
-                    self.stdout.queue(cursor::MoveTo(x0, y0))?;
+                    self.out.queue(cursor::MoveTo(x0, y0))?;
                 }
 
                 if fg0 != cur_fg {
                     if let Some(fg) = fg0 {
-                        self.stdout.queue(SetForegroundColor(fg))?;
+                        self.out.queue(SetForegroundColor(fg))?;
                     } else {
-                        self.stdout.queue(SetForegroundColor(Color::Reset))?;
+                        self.out.queue(SetForegroundColor(Color::Reset))?;
                     }
                     cur_fg = fg0;
                 }
 
                 if bg0 != cur_bg {
                     if let Some(bg) = bg0 {
-                        self.stdout.queue(SetBackgroundColor(bg))?;
+                        self.out.queue(SetBackgroundColor(bg))?;
                     } else {
-                        self.stdout.queue(SetBackgroundColor(Color::Reset))?;
+                        self.out.queue(SetBackgroundColor(Color::Reset))?;
                     }
                     cur_bg = bg0;
                 }
 
                 if bold0 != cur_bold {
-                    self.stdout.queue(SetAttribute(if bold0 {
+                    self.out.queue(SetAttribute(if bold0 {
                         Attribute::Bold
                     } else {
                         Attribute::NormalIntensity
@@ -281,7 +261,7 @@ impl Terminal {
                     cur_bold = bold0;
                 }
 
-                self.stdout.queue(Print(run_buf.as_str()))?;
+                self.out.queue(Print(run_buf.as_str()))?;
                 let next_x = x0.saturating_add(run_len);
                 cur_pos = if next_x < frame.width {
                     Some((next_x, y0))
@@ -294,28 +274,61 @@ impl Terminal {
             b.clear();
         }
 
-        self.stdout.queue(SetAttribute(Attribute::Reset))?;
-        self.stdout.queue(ResetColor)?;
-        self.stdout.flush()?;
+        self.out.queue(SetAttribute(Attribute::Reset))?;
+        self.out.queue(ResetColor)?;
         frame.clear_dirty();
         Ok(())
     }
 }
 
-impl Drop for Terminal {
-    fn drop(&mut self) {
-        let _ = self.stdout.execute(SetAttribute(Attribute::Reset));
-        let _ = self.stdout.execute(ResetColor);
-        let _ = self.stdout.execute(cursor::Show);
-        let _ = self.stdout.execute(terminal::EnableLineWrap);
-        let _ = self.stdout.execute(terminal::LeaveAlternateScreen);
-        let _ = terminal::disable_raw_mode();
-        let _ = self.stdout.flush();
+/// Where frames are drawn and input comes from.
+///
+/// [`Terminal`] is the real crossterm terminal; [`MemoryBackend`] records
+/// output in memory for tests and embedding.
+pub trait Backend {
+    /// Current size as `(cols, lines)`.
+    fn size(&self) -> Result<(u16, u16)>;
+
+    /// Draws the changed cells of `frame` and clears its dirty state.
+    fn draw(&mut self, frame: &mut Frame) -> Result<()>;
+
+    /// Waits up to `timeout` for an input event; true if one is ready.
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool>;
+
+    /// Reads the next input event; call after [`Backend::poll_event`]
+    /// returned true.
+    fn read_event(&mut self) -> Result<Event>;
+
+    /// Gives the screen back (leave raw mode, alternate screen, ...).
+    fn restore(&mut self) -> Result<()>;
+
+    /// Takes the screen again after [`Backend::restore`], e.g. when resuming
+    /// from Ctrl+Z. The next draw repaints everything.
+    fn resume(&mut self) -> Result<()> {
+        Ok(())
     }
 }
 
-pub fn restore_terminal_best_effort() {
-    let mut out = stdout();
+fn enter_screen(out: &mut Stdout) -> Result<()> {
+    terminal::enable_raw_mode()?;
+    let init_res: Result<()> = (|| {
+        out.execute(terminal::EnterAlternateScreen)?;
+        out.execute(cursor::Hide)?;
+        let _ = out.execute(terminal::DisableLineWrap);
+        out.execute(SetAttribute(Attribute::Reset))?;
+        out.execute(ResetColor)?;
+        out.execute(terminal::Clear(terminal::ClearType::All))?;
+        out.flush()?;
+        Ok(())
+    })();
+    if let Err(e) = init_res {
+        leave_screen(out);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn leave_screen(out: &mut Stdout) {
     let _ = out.execute(SetAttribute(Attribute::Reset));
     let _ = out.execute(ResetColor);
     let _ = out.execute(cursor::Show);
@@ -325,6 +338,208 @@ pub fn restore_terminal_best_effort() {
     let _ = out.flush();
 }
 
+/// The real terminal on stdout. Restores the terminal when dropped.
+pub struct Terminal {
+    stdout: Stdout,
+    renderer: Renderer,
+    recorder: Option<CastWriter<Box<dyn Write>>>,
+}
+
+impl Terminal {
+    /// Enters raw mode and the alternate screen.
+    pub fn new() -> Result<Self> {
+        let mut out = stdout();
+        enter_screen(&mut out)?;
+        Ok(Self {
+            stdout: out,
+            renderer: Renderer::new(),
+            recorder: None,
+        })
+    }
+
+    /// Also writes everything drawn, and every resize, to an asciicast v2
+    /// recording. The next draw repaints the whole screen so the recording
+    /// starts from a complete frame.
+    pub fn record(&mut self, recorder: CastWriter<Box<dyn Write>>) {
+        self.recorder = Some(recorder);
+        self.renderer.invalidate();
+    }
+}
+
+impl Backend for Terminal {
+    fn size(&self) -> Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn draw(&mut self, frame: &mut Frame) -> Result<()> {
+        self.renderer.draw(frame)?;
+        self.stdout.write_all(&self.renderer.out)?;
+        if let Some(rec) = self.recorder.as_mut() {
+            rec.output(&self.renderer.out)?;
+            rec.flush()?;
+        }
+        self.renderer.out.clear();
+        self.stdout.flush()
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        let ev = event::read()?;
+        if let (Event::Resize(w, h), Some(rec)) = (&ev, self.recorder.as_mut()) {
+            rec.resize(*w, *h)?;
+        }
+        Ok(ev)
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        leave_screen(&mut self.stdout);
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        self.renderer.invalidate();
+        enter_screen(&mut self.stdout)?;
+        if let Some(rec) = self.recorder.as_mut() {
+            let (w, h) = terminal::size()?;
+            rec.resize(w, h)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        leave_screen(&mut self.stdout);
+    }
+}
+
+/// In-memory backend: keeps the escape bytes a real terminal would have
+/// received and the resulting screen contents, and replays queued events.
+#[derive(Default)]
+pub struct MemoryBackend {
+    width: u16,
+    height: u16,
+    renderer: Option<Renderer>,
+    bytes: Vec<u8>,
+    screen: Vec<Cell>,
+    events: VecDeque<Event>,
+    draws: u64,
+    restored: bool,
+}
+
+impl MemoryBackend {
+    /// A blank `width` x `height` screen with no pending events.
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            renderer: Some(Renderer::new()),
+            screen: vec![blank_cell(None); width as usize * height as usize],
+            ..Self::default()
+        }
+    }
+
+    /// Queues an input event for [`Backend::read_event`].
+    pub fn push_event(&mut self, ev: Event) {
+        self.events.push_back(ev);
+    }
+
+    /// Changes the reported size and queues the matching resize event.
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.width = width;
+        self.height = height;
+        self.push_event(Event::Resize(width, height));
+    }
+
+    /// Every byte written so far.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns and clears the bytes written so far.
+    pub fn take_bytes(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.bytes)
+    }
+
+    /// Screen contents after the last draw, row-major.
+    pub fn cells(&self) -> &[Cell] {
+        &self.screen
+    }
+
+    /// The cell shown at `(x, y)` after the last draw.
+    pub fn cell(&self, x: u16, y: u16) -> Option<Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.screen
+            .get(y as usize * self.width as usize + x as usize)
+            .copied()
+    }
+
+    /// Number of [`Backend::draw`] calls.
+    pub fn draws(&self) -> u64 {
+        self.draws
+    }
+
+    /// True after [`Backend::restore`] until [`Backend::resume`].
+    pub fn is_restored(&self) -> bool {
+        self.restored
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok((self.width, self.height))
+    }
+
+    fn draw(&mut self, frame: &mut Frame) -> Result<()> {
+        let renderer = self.renderer.get_or_insert_with(Renderer::new);
+        renderer.draw(frame)?;
+        self.bytes.extend_from_slice(&renderer.out);
+        renderer.out.clear();
+        if let Some(last) = &renderer.last {
+            self.screen.clone_from(&last.cells);
+        }
+        self.draws += 1;
+        Ok(())
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<bool> {
+        if self.events.is_empty() && !timeout.is_zero() {
+            std::thread::sleep(timeout);
+        }
+        Ok(!self.events.is_empty())
+    }
+
+    fn read_event(&mut self) -> Result<Event> {
+        self.events
+            .pop_front()
+            .ok_or_else(|| Error::new(ErrorKind::WouldBlock, "no pending events"))
+    }
+
+    fn restore(&mut self) -> Result<()> {
+        self.restored = true;
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        self.restored = false;
+        if let Some(r) = self.renderer.as_mut() {
+            r.invalidate();
+        }
+        Ok(())
+    }
+}
+
+/// Leaves raw mode and the alternate screen, ignoring errors. Safe to call
+/// from signal and panic handlers.
+pub fn restore_terminal_best_effort() {
+    leave_screen(&mut stdout());
+}
+
 pub fn blank_cell(bg: Option<Color>) -> Cell {
     Cell {
         ch: ' ',
@@ -333,3 +548,32 @@ pub fn blank_cell(bg: Option<Color>) -> Cell {
         bold: false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_backend_records_only_changed_cells() {
+        let mut term = MemoryBackend::new(4, 2);
+        let mut frame = Frame::new(4, 2, None);
+        let cell = Cell {
+            ch: 'x',
+            fg: Some(Color::Green),
+            bg: None,
+            bold: false,
+        };
+        frame.set(1, 1, cell);
+        term.draw(&mut frame).unwrap();
+        assert_eq!(term.cell(1, 1), Some(cell));
+        let full = term.take_bytes();
+
+        frame.set(2, 0, Cell { ch: 'y', ..cell });
+        term.draw(&mut frame).unwrap();
+        let diff = term.take_bytes();
+        assert!(diff.len() < full.len());
+        assert!(String::from_utf8(diff).unwrap().contains('y'));
+        assert_eq!(term.cell(2, 0).map(|c| c.ch), Some('y'));
+        assert_eq!(term.draws(), 2);
+    }
+}
@@ -0,0 +1,315 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! User-defined themes (`themes.toml`).
+//!
+//! Each `[theme.NAME]` table describes a dark-to-bright gradient:
+//!
+//! ```toml
+//! [theme.sakura]
+//! description = "Cherry blossom"
+//! stops = ["#1a0010", "#b03070", [255, 180, 210]]
+//! steps = 9          # optional, default 9
+//! head = "#ffffff"   # optional, drawn only at droplet heads
+//!
+//! [theme.desk]
+//! import = "gruvbox-dark.yaml"  # terminal scheme instead of `stops`
+//! accent = "green"              # optional ANSI color to build the ramp on
+//! ```
+//!
+//! Imported schemes are read with the `theme_import` module; relative paths
+//! are resolved against the theme file's directory.
+//!
+//! Loaded themes are passed to [`parse_color_scheme_with`] and then behave
+//! like built-in ones: they are carried by [`ColorScheme::Custom`] and built
+//! by [`build_palette`](crate::palette::build_palette).
+//!
+//! [`ColorScheme::Custom`]: crate::runtime::ColorScheme::Custom
+//! [`parse_color_scheme_with`]: crate::runtime::parse_color_scheme_with
+
+use std::path::Path;
+
+use crate::runtime::parse_builtin_color_scheme;
+use crate::theme_import::{Ramp, SchemeColors, SchemeFormat, ACCENTS};
+
+pub const DEFAULT_STEPS: usize = 9;
+pub const MAX_STEPS: usize = 64;
+pub const MAX_THEMES: usize = 256;
+
+/// A gradient theme from the theme file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomTheme {
+    pub name: String,
+    pub description: Option<String>,
+    pub stops: Vec<(u8, u8, u8)>,
+    pub steps: usize,
+    pub head: Option<(u8, u8, u8)>,
+}
+
+/// Parses `#RRGGBB` (the `#` is optional).
+pub fn parse_hex_rgb(s: &str) -> Result<(u8, u8, u8), String> {
+    let hex = s.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("invalid color {:?} (expected #RRGGBB)", s));
+    }
+    let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).unwrap_or(0);
+    Ok((byte(0), byte(2), byte(4)))
+}
+
+fn parse_rgb_value(v: &toml::Value) -> Result<(u8, u8, u8), String> {
+    match v {
+        toml::Value::String(s) => parse_hex_rgb(s),
+        toml::Value::Array(items) => {
+            let parts: Option<Vec<u8>> = items
+                .iter()
+                .map(|i| i.as_integer().and_then(|n| u8::try_from(n).ok()))
+                .collect();
+            match parts.as_deref() {
+                Some(&[r, g, b]) => Ok((r, g, b)),
+                _ => Err("invalid color (expected [r, g, b] with values 0-255)".to_string()),
+            }
+        }
+        _ => Err("invalid color (expected \"#RRGGBB\" or [r, g, b])".to_string()),
+    }
+}
+
+/// Reads the terminal scheme at `path` and builds stops around `accent`.
+fn import_scheme(path: &Path, accent: &str) -> Result<(Option<String>, Ramp), String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let format = SchemeFormat::detect(path, &text).ok_or_else(|| {
+        format!(
+            "{}: unknown scheme format (expected base16 YAML, .itermcolors, Alacritty TOML or Kitty .conf)",
+            path.display()
+        )
+    })?;
+    let scheme =
+        SchemeColors::parse(format, &text).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let accent = ACCENTS
+        .iter()
+        .position(|&a| a == accent.trim().to_ascii_lowercase())
+        .ok_or_else(|| {
+            format!(
+                "unknown accent {:?} (one of {})",
+                accent,
+                ACCENTS.join(", ")
+            )
+        })?;
+    let ramp = scheme
+        .ramp(accent)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok((scheme.name, ramp))
+}
+
+fn parse_theme(name: &str, t: &toml::Table, dir: &Path) -> Result<CustomTheme, String> {
+    let key = |k: &str| format!("theme.{}.{}", name, k);
+
+    for k in t.keys() {
+        if !matches!(
+            k.as_str(),
+            "description" | "stops" | "steps" | "head" | "import" | "accent"
+        ) {
+            return Err(format!("unknown key `{}`", key(k)));
+        }
+    }
+
+    let string = |k: &str| match t.get(k) {
+        None => Ok(None),
+        Some(toml::Value::String(s)) => Ok(Some(s.as_str())),
+        Some(_) => Err(format!("{}: expected a string", key(k))),
+    };
+
+    let mut description = string("description")?.map(str::to_string);
+
+    let imported = match (string("import")?, t.contains_key("stops")) {
+        (Some(_), true) => {
+            return Err(format!(
+                "{}: use either `stops` or `import`, not both",
+                key("import")
+            ))
+        }
+        (Some(file), false) => {
+            let accent = string("accent")?.unwrap_or("green");
+            let (scheme_name, ramp) = import_scheme(&dir.join(file), accent)
+                .map_err(|e| format!("{}: {}", key("import"), e))?;
+            description = description.or(scheme_name);
+            Some(ramp)
+        }
+        (None, _) if t.contains_key("accent") => {
+            return Err(format!("{}: only valid with `import`", key("accent")))
+        }
+        (None, _) => None,
+    };
+
+    let stops = match t.get("stops") {
+        _ if imported.is_some() => imported
+            .as_ref()
+            .map(|(s, _)| s.clone())
+            .unwrap_or_default(),
+        Some(toml::Value::Array(items)) if !items.is_empty() => items
+            .iter()
+            .map(parse_rgb_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("{}: {}", key("stops"), e))?,
+        Some(_) => {
+            return Err(format!(
+                "{}: expected a non-empty list of colors",
+                key("stops")
+            ))
+        }
+        None => {
+            return Err(format!(
+                "{}: missing (list of colors, or `import` a terminal scheme)",
+                key("stops")
+            ))
+        }
+    };
+
+    let steps = match t.get("steps") {
+        None => DEFAULT_STEPS,
+        Some(toml::Value::Integer(n)) if (1..=MAX_STEPS as i64).contains(n) => *n as usize,
+        Some(_) => {
+            return Err(format!(
+                "{}: expected a number (min 1 max {})",
+                key("steps"),
+                MAX_STEPS
+            ))
+        }
+    };
+
+    let head = t
+        .get("head")
+        .map(parse_rgb_value)
+        .transpose()
+        .map_err(|e| format!("{}: {}", key("head"), e))?
+        .or(imported.and_then(|(_, h)| h));
+
+    Ok(CustomTheme {
+        name: name.to_string(),
+        description,
+        stops,
+        steps,
+        head,
+    })
+}
+
+/// Parses the `[theme.NAME]` tables of a theme file, sorted by name.
+/// `import` paths are relative to `dir`.
+pub fn parse_theme_file(text: &str, dir: &Path) -> Result<Vec<CustomTheme>, String> {
+    let table = text
+        .parse::<toml::Table>()
+        .map_err(|e| e.message().to_string())?;
+
+    for k in table.keys() {
+        if k != "theme" {
+            return Err(format!("unknown key `{}` (themes go in [theme.NAME])", k));
+        }
+    }
+    let Some(themes) = table.get("theme") else {
+        return Ok(Vec::new());
+    };
+    let Some(themes) = themes
+        .as_table()
+        .filter(|t| t.values().all(|v| v.is_table()))
+    else {
+        return Err("themes must be tables, e.g. [theme.sakura]".to_string());
+    };
+    if themes.len() > MAX_THEMES {
+        return Err(format!("too many themes (max {})", MAX_THEMES));
+    }
+
+    let mut out: Vec<CustomTheme> = Vec::with_capacity(themes.len());
+    for (name, t) in themes {
+        let lower = name.to_ascii_lowercase();
+        if parse_builtin_color_scheme(&lower).is_some() {
+            return Err(format!("theme.{}: name is taken by a built-in theme", name));
+        }
+        if out.iter().any(|c| c.name.to_ascii_lowercase() == lower) {
+            return Err(format!("theme.{}: defined twice", name));
+        }
+        out.push(parse_theme(
+            name,
+            t.as_table().expect("checked above"),
+            dir,
+        )?);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_stops_steps_and_head() {
+        let themes = parse_theme_file(
+            "[theme.sakura]\ndescription = \"Cherry\"\nstops = [\"#1a0010\", [255, 180, 210]]\n\
+             steps = 5\nhead = \"fff0f8\"\n\n[theme.zen]\nstops = [\"#00ff00\"]\n",
+            Path::new(""),
+        )
+        .unwrap();
+        assert_eq!(themes.len(), 2);
+        assert_eq!(themes[0].stops, vec![(0x1a, 0, 0x10), (255, 180, 210)]);
+        assert_eq!(
+            (themes[0].steps, themes[0].head),
+            (5, Some((255, 240, 248)))
+        );
+        assert_eq!((themes[1].steps, themes[1].head), (DEFAULT_STEPS, None));
+
+        let err =
+            parse_theme_file("[theme.fire]\nstops = [\"#ff0000\"]\n", Path::new("")).unwrap_err();
+        assert!(err.contains("built-in"), "{}", err);
+        let err = parse_theme_file("[theme.x]\nstops = [\"#ff00\"]\n", Path::new("")).unwrap_err();
+        assert!(err.starts_with("theme.x.stops:"), "{}", err);
+    }
+
+    #[test]
+    fn custom_themes_behave_like_built_ins() {
+        use std::sync::Arc;
+
+        use crate::palette::build_palette;
+        use crate::runtime::{
+            cycle_color_scheme_in, parse_color_scheme, parse_color_scheme_with, ColorMode,
+            ColorScheme,
+        };
+        use crossterm::style::Color;
+
+        let themes: Vec<Arc<CustomTheme>> = parse_theme_file(
+            "[theme.Sakura]\nstops = [\"#200010\", \"#ff80c0\"]\nsteps = 4\nhead = \"#ffffff\"\n",
+            Path::new(""),
+        )
+        .unwrap()
+        .into_iter()
+        .map(Arc::new)
+        .collect();
+        let sakura = parse_color_scheme_with("sakura", &themes).unwrap();
+        assert_eq!(sakura, ColorScheme::Custom(Arc::clone(&themes[0])));
+        assert!(parse_color_scheme("sakura").is_err());
+        assert!(matches!(
+            parse_color_scheme_with("sakura+fire", &themes),
+            Ok(ColorScheme::Blend(_))
+        ));
+        let list = [ColorScheme::Magma, sakura.clone()];
+        assert_eq!(cycle_color_scheme_in(&list, &ColorScheme::Magma, 1), sakura);
+        assert_eq!(cycle_color_scheme_in(&list, &sakura, 1), ColorScheme::Magma);
+
+        let p = build_palette(&sakura, ColorMode::TrueColor, false);
+        assert_eq!(p.colors.len(), 5);
+        assert_eq!(p.colors[0], Color::Rgb { r: 32, g: 0, b: 16 });
+        assert_eq!(
+            p.colors[4],
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        for mode in [ColorMode::Color256, ColorMode::Color16] {
+            assert_eq!(build_palette(&sakura, mode, false).colors.len(), 5);
+        }
+        assert_eq!(
+            build_palette(&sakura, ColorMode::Mono, false).colors,
+            vec![Color::White]
+        );
+    }
+}
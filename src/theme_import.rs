@@ -0,0 +1,378 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Importers for terminal color schemes: base16/base24 YAML, iTerm2
+//! `.itermcolors` plists, Alacritty TOML and Kitty `.conf` files.
+//!
+//! A scheme is read into [`SchemeColors`] (background, foreground and the 16
+//! ANSI colors) and turned into gradient stops by [`SchemeColors::ramp`]:
+//! the darkest background color, then the normal and bright variant of the
+//! chosen accent, sorted dark to bright, with the brightest text color as
+//! the droplet head.
+
+use std::path::Path;
+
+use crate::theme::parse_hex_rgb;
+
+type Rgb = (u8, u8, u8);
+
+/// Gradient stops (dark to bright) and an optional head color.
+pub type Ramp = (Vec<Rgb>, Option<Rgb>);
+
+/// ANSI color names, in ANSI order (index 0-7).
+pub const ACCENTS: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Supported scheme file formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemeFormat {
+    Base16,
+    ITerm2,
+    Alacritty,
+    Kitty,
+}
+
+impl SchemeFormat {
+    /// Guesses the format from the file extension, then from the contents.
+    pub fn detect(path: &Path, text: &str) -> Option<Self> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        match ext.as_deref() {
+            Some("yaml" | "yml") => return Some(Self::Base16),
+            Some("itermcolors") => return Some(Self::ITerm2),
+            Some("toml") => return Some(Self::Alacritty),
+            Some("conf") => return Some(Self::Kitty),
+            _ => {}
+        }
+        if text.contains("<plist") {
+            Some(Self::ITerm2)
+        } else if text.contains("base00") {
+            Some(Self::Base16)
+        } else if text.contains("[colors") {
+            Some(Self::Alacritty)
+        } else if text.lines().any(|l| l.trim_start().starts_with("color0")) {
+            Some(Self::Kitty)
+        } else {
+            None
+        }
+    }
+}
+
+/// The colors of a terminal scheme that matter for the rain.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemeColors {
+    pub name: Option<String>,
+    pub background: Option<Rgb>,
+    pub foreground: Option<Rgb>,
+    /// ANSI colors 0-7.
+    pub normal: [Option<Rgb>; 8],
+    /// ANSI colors 8-15.
+    pub bright: [Option<Rgb>; 8],
+}
+
+/// Relative luminance (Rec. 709 weights on sRGB values), for ordering.
+fn luma((r, g, b): Rgb) -> u32 {
+    2126 * r as u32 + 7152 * g as u32 + 722 * b as u32
+}
+
+/// Accepts `#RRGGBB`, `RRGGBB` and Alacritty's `0xRRGGBB`.
+fn parse_color(s: &str) -> Result<Rgb, String> {
+    let s = s.trim().trim_matches(|c| c == '"' || c == '\'');
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => parse_hex_rgb(hex),
+        None => parse_hex_rgb(s),
+    }
+}
+
+impl SchemeColors {
+    /// Parses `text` as `format`.
+    pub fn parse(format: SchemeFormat, text: &str) -> Result<Self, String> {
+        let colors = match format {
+            SchemeFormat::Base16 => parse_base16(text)?,
+            SchemeFormat::ITerm2 => parse_itermcolors(text)?,
+            SchemeFormat::Alacritty => parse_alacritty(text)?,
+            SchemeFormat::Kitty => parse_kitty(text)?,
+        };
+        if colors
+            .normal
+            .iter()
+            .chain(&colors.bright)
+            .all(Option::is_none)
+        {
+            return Err("no ANSI colors found".to_string());
+        }
+        Ok(colors)
+    }
+
+    /// Gradient stops (dark to bright) and head color built around the
+    /// ANSI color `accent` (index into [`ACCENTS`]).
+    pub fn ramp(&self, accent: usize) -> Result<Ramp, String> {
+        let accent = accent.min(7);
+        let (normal, bright) = (self.normal[accent], self.bright[accent]);
+        if normal.is_none() && bright.is_none() {
+            return Err(format!("scheme has no {} color", ACCENTS[accent]));
+        }
+
+        let dark = [self.background, self.normal[0]]
+            .into_iter()
+            .flatten()
+            .min_by_key(|&c| luma(c));
+        let mut stops: Vec<Rgb> = [normal, bright].into_iter().flatten().collect();
+        stops.sort_by_key(|&c| luma(c));
+        stops.dedup();
+        if let Some(d) = dark.filter(|&d| luma(d) < luma(stops[0])) {
+            stops.insert(0, d);
+        }
+
+        let top = luma(*stops.last().expect("non-empty"));
+        let head = [self.bright[7], self.foreground, self.normal[7]]
+            .into_iter()
+            .flatten()
+            .max_by_key(|&c| luma(c))
+            .filter(|&c| luma(c) > top);
+        Ok((stops, head))
+    }
+}
+
+/// Base16 (`base00`-`base0F`) and base24 (plus `base10`-`base17`) YAML, in
+/// both the classic flat layout and the newer `palette:` one.
+fn parse_base16(text: &str) -> Result<SchemeColors, String> {
+    let mut base: [Option<Rgb>; 24] = [None; 24];
+    let mut name = None;
+    for (n, line) in text.lines().enumerate() {
+        let line = line.split(" #").next().unwrap_or_default().trim();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if matches!(key, "scheme" | "name") && name.is_none() && !value.is_empty() {
+            name = Some(value.trim_matches(|c| c == '"' || c == '\'').to_string());
+            continue;
+        }
+        let Some(idx) = key
+            .strip_prefix("base")
+            .filter(|h| h.len() == 2)
+            .and_then(|h| usize::from_str_radix(h, 16).ok())
+            .filter(|&i| i < base.len())
+        else {
+            continue;
+        };
+        base[idx] = Some(parse_color(value).map_err(|e| format!("line {}: {}", n + 1, e))?);
+    }
+    if base[..16].iter().any(Option::is_none) {
+        return Err("expected base00 to base0F".to_string());
+    }
+
+    let b = |i: usize| base[i];
+    let or = |i: usize, fallback: usize| base[i].or(base[fallback]);
+    Ok(SchemeColors {
+        name,
+        background: b(0x00),
+        foreground: b(0x05),
+        normal: [
+            b(0x00),
+            b(0x08),
+            b(0x0B),
+            b(0x0A),
+            b(0x0D),
+            b(0x0E),
+            b(0x0C),
+            b(0x05),
+        ],
+        bright: [
+            b(0x03),
+            or(0x12, 0x08),
+            or(0x14, 0x0B),
+            or(0x13, 0x0A),
+            or(0x16, 0x0D),
+            or(0x17, 0x0E),
+            or(0x15, 0x0C),
+            b(0x07),
+        ],
+    })
+}
+
+/// Text of the next `<tag>...</tag>` at or after `from`, and the offset just
+/// past it.
+fn xml_element<'a>(text: &'a str, tag: &str, from: usize) -> Option<(&'a str, usize)> {
+    let open = format!("<{}>", tag);
+    let close = format!("</{}>", tag);
+    let start = text[from..].find(&open)? + from + open.len();
+    let end = text[start..].find(&close)? + start;
+    Some((&text[start..end], end + close.len()))
+}
+
+/// iTerm2 `.itermcolors`: a plist dict of `Ansi N Color`, `Background Color`
+/// and `Foreground Color` entries with 0-1 `Red/Green/Blue Component`s.
+fn parse_itermcolors(text: &str) -> Result<SchemeColors, String> {
+    let mut out = SchemeColors::default();
+    let mut pos = 0;
+    while let Some((key, after_key)) = xml_element(text, "key", pos) {
+        pos = after_key;
+        let rest = text[after_key..].trim_start();
+        if !rest.starts_with("<dict>") {
+            continue;
+        }
+        let Some((dict, after_dict)) = xml_element(text, "dict", after_key) else {
+            break;
+        };
+        pos = after_dict;
+
+        let mut rgb = [None::<f64>; 3];
+        let mut p = 0;
+        while let Some((k, after)) = xml_element(dict, "key", p) {
+            p = after;
+            let slot = match k.trim() {
+                "Red Component" => 0,
+                "Green Component" => 1,
+                "Blue Component" => 2,
+                _ => continue,
+            };
+            // The value is the element right after the key, before the next one.
+            let value = &dict[after
+                ..dict[after..]
+                    .find("<key>")
+                    .map_or(dict.len(), |i| i + after)];
+            rgb[slot] =
+                xml_element(value, "real", 0).and_then(|(v, _)| v.trim().parse::<f64>().ok());
+        }
+        let [Some(r), Some(g), Some(b)] = rgb else {
+            return Err(format!("{}: missing color components", key.trim()));
+        };
+        let c = |v: f64| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+        let color = Some((c(r), c(g), c(b)));
+
+        let key = key.trim();
+        match key {
+            "Background Color" => out.background = color,
+            "Foreground Color" => out.foreground = color,
+            _ => {
+                if let Some(n) = key
+                    .strip_prefix("Ansi ")
+                    .and_then(|k| k.strip_suffix(" Color"))
+                    .and_then(|n| n.parse::<usize>().ok())
+                {
+                    match n {
+                        0..=7 => out.normal[n] = color,
+                        8..=15 => out.bright[n - 8] = color,
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Alacritty TOML: `[colors.primary]`, `[colors.normal]` and
+/// `[colors.bright]` tables.
+fn parse_alacritty(text: &str) -> Result<SchemeColors, String> {
+    let table = text
+        .parse::<toml::Table>()
+        .map_err(|e| e.message().to_string())?;
+    let colors = table
+        .get("colors")
+        .and_then(|c| c.as_table())
+        .ok_or("missing [colors] table")?;
+    let get = |section: &str, key: &str| -> Result<Option<Rgb>, String> {
+        match colors.get(section).and_then(|s| s.get(key)) {
+            None => Ok(None),
+            Some(toml::Value::String(s)) => parse_color(s)
+                .map(Some)
+                .map_err(|e| format!("colors.{}.{}: {}", section, key, e)),
+            Some(_) => Err(format!("colors.{}.{}: expected a string", section, key)),
+        }
+    };
+
+    let mut out = SchemeColors {
+        background: get("primary", "background")?,
+        foreground: get("primary", "foreground")?,
+        ..SchemeColors::default()
+    };
+    for (i, name) in ACCENTS.iter().enumerate() {
+        out.normal[i] = get("normal", name)?;
+        out.bright[i] = get("bright", name)?;
+    }
+    Ok(out)
+}
+
+/// Kitty `.conf`: `background`, `foreground` and `color0`-`color15` lines.
+fn parse_kitty(text: &str) -> Result<SchemeColors, String> {
+    let mut out = SchemeColors::default();
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let slot = match key {
+            "background" => &mut out.background,
+            "foreground" => &mut out.foreground,
+            _ => match key
+                .strip_prefix("color")
+                .and_then(|n| n.parse::<usize>().ok())
+            {
+                Some(i @ 0..=7) => &mut out.normal[i],
+                Some(i @ 8..=15) => &mut out.bright[i - 8],
+                _ => continue,
+            },
+        };
+        *slot = Some(parse_color(value).map_err(|e| format!("line {}: {}", n + 1, e))?);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_format_yields_a_dark_to_bright_ramp() {
+        let base16 = "scheme: \"Test\"\nbase00: \"101010\"\nbase01: \"202020\"\n\
+             base02: \"303030\"\nbase03: \"404040\"\nbase04: \"505050\"\nbase05: \"c0c0c0\"\n\
+             base06: \"d0d0d0\"\nbase07: \"f0f0f0\"\nbase08: \"cc0000\"\nbase09: \"cc6600\"\n\
+             base0A: \"cccc00\"\nbase0B: \"00aa00\"\nbase0C: \"00aaaa\"\nbase0D: \"0000cc\"\n\
+             base0E: \"aa00aa\"\nbase0F: \"663300\"\nbase14: \"33ff33\"\n";
+        let iterm = "<plist><dict>\n<key>Ansi 2 Color</key>\n<dict>\n\
+             <key>Blue Component</key><real>0</real>\n<key>Color Space</key><string>sRGB</string>\n<key>Green Component</key><real>0.6666</real>\n\
+             <key>Red Component</key><real>0</real>\n</dict>\n<key>Ansi 10 Color</key>\n<dict>\n\
+             <key>Blue Component</key><real>0.2</real><key>Green Component</key><real>1</real>\n\
+             <key>Red Component</key><real>0.2</real></dict>\n<key>Background Color</key><dict>\n\
+             <key>Blue Component</key><real>0.0627</real><key>Green Component</key><real>0.0627</real>\n\
+             <key>Red Component</key><real>0.0627</real></dict>\n</dict></plist>\n";
+        let alacritty = "[colors.primary]\nbackground = \"#101010\"\nforeground = \"0xf0f0f0\"\n\
+             [colors.normal]\ngreen = \"#00aa00\"\n[colors.bright]\ngreen = \"#33ff33\"\n";
+        let kitty = "# test\nbackground #101010\nforeground #f0f0f0\ncolor2 #00aa00\n\
+             color10 #33ff33\n";
+
+        for (format, text) in [
+            (SchemeFormat::Base16, base16),
+            (SchemeFormat::ITerm2, iterm),
+            (SchemeFormat::Alacritty, alacritty),
+            (SchemeFormat::Kitty, kitty),
+        ] {
+            let scheme = SchemeColors::parse(format, text).unwrap();
+            let (stops, _) = scheme.ramp(2).unwrap();
+            assert_eq!(
+                stops,
+                vec![(16, 16, 16), (0, 170, 0), (51, 255, 51)],
+                "{:?}",
+                format
+            );
+        }
+        let (_, head) = SchemeColors::parse(SchemeFormat::Kitty, kitty)
+            .unwrap()
+            .ramp(2)
+            .unwrap();
+        assert_eq!(head, Some((240, 240, 240)));
+        assert!(SchemeColors::parse(SchemeFormat::Kitty, kitty)
+            .unwrap()
+            .ramp(1)
+            .is_err());
+    }
+}
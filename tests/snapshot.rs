@@ -0,0 +1,178 @@
+// Copyright (c) 2026 rezky_nightky
+
+//! Golden snapshots of the rain, stepped with virtual time.
+//!
+//! Each scenario builds a seeded [`Cloud`], advances it with synthetic
+//! `Instant`s and dumps selected frames as text plus a color layer (the
+//! palette index of every cell's foreground). The dumps are compared with
+//! `tests/golden/<scenario>.txt`; run with `COSMOSTRIX_BLESS=1` to rewrite
+//! them after an intended change and review the diff.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use cosmostrix::{BoldMode, Cloud, ColorMode, ColorScheme, Frame, ShadingMode};
+
+const COLS: u16 = 24;
+const LINES: u16 = 10;
+const TICK: Duration = Duration::from_millis(40);
+const SNAPSHOT_TICKS: [u32; 3] = [10, 25, 50];
+const INDEX_DIGITS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+struct Scenario {
+    name: &'static str,
+    color_mode: ColorMode,
+    shading_mode: ShadingMode,
+    bold_mode: BoldMode,
+    async_mode: bool,
+    color_scheme: ColorScheme,
+    seed: u64,
+    message: Option<&'static str>,
+}
+
+fn color_layer(cloud: &Cloud, frame: &Frame, y: u16) -> String {
+    (0..frame.width)
+        .map(|x| {
+            let cell = frame.cell_at_index(y as usize * frame.width as usize + x as usize);
+            match cell.fg {
+                None => '.',
+                Some(fg) => match cloud.palette().colors.iter().position(|&c| c == fg) {
+                    Some(i) if i < INDEX_DIGITS.len() => INDEX_DIGITS[i] as char,
+                    _ => '?',
+                },
+            }
+        })
+        .collect()
+}
+
+fn dump(cloud: &Cloud, frame: &Frame, tick: u32, out: &mut String) {
+    let _ = writeln!(
+        out,
+        "== tick {} ({} ms)",
+        tick,
+        tick * TICK.as_millis() as u32
+    );
+    for y in 0..frame.height {
+        let text: String = (0..frame.width)
+            .map(|x| {
+                frame
+                    .cell_at_index(y as usize * frame.width as usize + x as usize)
+                    .ch
+            })
+            .collect();
+        let _ = writeln!(out, "|{}|  |{}|", text, color_layer(cloud, frame, y));
+    }
+}
+
+fn render(s: &Scenario) -> String {
+    let mut cloud = Cloud::builder()
+        .color_mode(s.color_mode)
+        .shading_mode(s.shading_mode)
+        .bold_mode(s.bold_mode)
+        .async_mode(s.async_mode)
+        .color_scheme(s.color_scheme.clone())
+        .seed(s.seed)
+        .build();
+    cloud.init_chars("0123456789abcdef".chars().collect());
+    let start = Instant::now();
+    cloud.reset_at(COLS, LINES, start);
+    if let Some(msg) = s.message {
+        cloud.set_message(msg);
+    }
+    let mut frame = Frame::new(COLS, LINES, cloud.palette().bg);
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "# {}: {}x{} seed {} (text | palette index)",
+        s.name, COLS, LINES, s.seed
+    );
+    for tick in 1..=*SNAPSHOT_TICKS.last().expect("non-empty") {
+        cloud.rain_at(&mut frame, start + TICK * tick);
+        frame.clear_dirty();
+        if SNAPSHOT_TICKS.contains(&tick) {
+            dump(&cloud, &frame, tick, &mut out);
+        }
+    }
+    out
+}
+
+fn check(s: Scenario) {
+    let actual = render(&s);
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden"]
+        .iter()
+        .collect::<PathBuf>()
+        .join(format!("{}.txt", s.name));
+
+    if std::env::var_os("COSMOSTRIX_BLESS").is_some_and(|v| v != "0") {
+        std::fs::create_dir_all(path.parent().expect("has parent")).unwrap();
+        std::fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "{}: {} (run with COSMOSTRIX_BLESS=1 to create it)",
+            path.display(),
+            e
+        )
+    });
+    if expected != actual {
+        let line = expected
+            .lines()
+            .zip(actual.lines())
+            .position(|(a, b)| a != b)
+            .unwrap_or_else(|| expected.lines().count().min(actual.lines().count()));
+        panic!(
+            "{} differs from the current output at line {}:\n  expected: {}\n  actual:   {}\n\
+             re-run with COSMOSTRIX_BLESS=1 if the change is intended, then review the diff",
+            path.display(),
+            line + 1,
+            expected.lines().nth(line).unwrap_or("<eof>"),
+            actual.lines().nth(line).unwrap_or("<eof>"),
+        );
+    }
+}
+
+#[test]
+fn green_distance_shading() {
+    check(Scenario {
+        name: "green_distance",
+        color_mode: ColorMode::TrueColor,
+        shading_mode: ShadingMode::DistanceFromHead,
+        bold_mode: BoldMode::Random,
+        async_mode: false,
+        color_scheme: ColorScheme::Green,
+        seed: 42,
+        message: None,
+    });
+}
+
+#[test]
+fn rainbow_random_shading_async() {
+    check(Scenario {
+        name: "rainbow_random_async",
+        color_mode: ColorMode::Color256,
+        shading_mode: ShadingMode::Random,
+        bold_mode: BoldMode::Off,
+        async_mode: true,
+        color_scheme: ColorScheme::Rainbow,
+        seed: 7,
+        message: None,
+    });
+}
+
+#[test]
+fn fire_with_message() {
+    check(Scenario {
+        name: "fire_message",
+        color_mode: ColorMode::TrueColor,
+        shading_mode: ShadingMode::DistanceFromHead,
+        bold_mode: BoldMode::All,
+        async_mode: false,
+        color_scheme: ColorScheme::Fire,
+        seed: 1234,
+        message: Some("WAKE UP"),
+    });
+}